@@ -0,0 +1,163 @@
+use crate::{
+    buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph::Point, glyph_record::Glyph,
+};
+use serde::Serialize;
+
+/// The name and color of an anchor, from the "Glyph Anchors Supplemental" entry
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct AnchorProperties {
+    pub name: String,
+    /// The color as 0x00BBGGRR, or None for the default color
+    pub color: Option<u32>,
+}
+
+/// An anchor of a glyph with its position for each master
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Anchor {
+    pub name: Option<String>,
+    /// The color as 0x00BBGGRR, or None for the default color
+    pub color: Option<u32>,
+    pub points: Vec<Point>,
+}
+
+impl Anchor {
+    /// The position of the anchor in the given master
+    pub fn point(&self, master: usize) -> Option<Point> {
+        self.points.get(master).copied()
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_anchors_supplemental(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let num_anchors = self.read_count()?;
+        let mut anchors = vec![];
+        for _ in 0..num_anchors {
+            let name = self.read_counted_str()?;
+            let color = u32::try_from(self.read_value()?).ok();
+            anchors.push(AnchorProperties { name, color });
+        }
+        Ok(Some(VfbEntryType::AnchorsSupplemental(anchors)))
+    }
+
+    pub fn decompile_anchors_mm(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let num_anchors = self.read_count()?;
+        let mut anchors = vec![];
+        for _ in 0..num_anchors {
+            let points = (0..self.master_count())
+                .map(|_| self.read_point())
+                .collect::<Result<Vec<Point>, VfbError>>()?;
+            anchors.push(points);
+        }
+        Ok(Some(VfbEntryType::AnchorsMM(anchors)))
+    }
+}
+
+impl Glyph {
+    /// The anchors of the glyph, with the positions from "Glyph Anchors MM" and the
+    /// names and colors from "Glyph Anchors Supplemental", which are matched by index
+    pub fn anchors(&self) -> Result<Vec<Anchor>, VfbError> {
+        let positions = match self.entry("Glyph Anchors MM").map(|e| &e.entry) {
+            Some(VfbEntryType::AnchorsMM(positions)) => positions.as_slice(),
+            _ => &[],
+        };
+        let properties = match self.entry("Glyph Anchors Supplemental").map(|e| &e.entry) {
+            Some(VfbEntryType::AnchorsSupplemental(properties)) => Some(properties),
+            _ => None,
+        };
+        if let Some(properties) = properties {
+            if properties.len() != positions.len() {
+                return Err(VfbError::BadValue(
+                    format!(
+                        "{} anchor names for {} anchors in glyph {}",
+                        properties.len(),
+                        positions.len(),
+                        self.name().unwrap_or_default()
+                    ),
+                    "a name for each anchor".to_string(),
+                ));
+            }
+        }
+        Ok(positions
+            .iter()
+            .enumerate()
+            .map(|(i, points)| {
+                let props = properties.and_then(|p| p.get(i));
+                Anchor {
+                    name: props.map(|p| p.name.clone()),
+                    color: props.and_then(|p| p.color),
+                    points: points.clone(),
+                }
+            })
+            .collect())
+    }
+
+    /// The anchor with the given name
+    pub fn anchor(&self, name: &str) -> Result<Option<Anchor>, VfbError> {
+        Ok(self
+            .anchors()?
+            .into_iter()
+            .find(|a| a.name.as_deref() == Some(name)))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        glyph::{tests::glyph_data, Point},
+        tests::vfb_with,
+        Vfb,
+    };
+
+    /// The anchors "top" with a red color and "bottom"
+    pub(crate) fn anchors_supplemental_data() -> Vec<u8> {
+        vec![
+            0x8d, // 2 anchors
+            0x8e, b't', b'o', b'p', 0xf7, 0x93, // top
+            0x91, b'b', b'o', b't', b't', b'o', b'm', 0x8a, // bottom
+        ]
+    }
+
+    /// Positions for 2 anchors in 2 masters
+    pub(crate) fn anchors_mm_data() -> Vec<u8> {
+        vec![
+            0x8d, // 2 anchors
+            0xef, 0xf7, 0xec, 0xf0, 0xf7, 0xf6, // top: (100, 344), (101, 354)
+            0xef, 0x8b, 0xf0, 0x8b, // bottom: (100, 0), (101, 0)
+        ]
+    }
+
+    fn vfb(supplemental: &[u8]) -> Vfb {
+        vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2020, supplemental.to_vec()),
+            (2029, anchors_mm_data()),
+        ])
+    }
+
+    #[test]
+    fn test_anchors() {
+        let vfb = vfb(&anchors_supplemental_data());
+        let glyph = vfb.glyph("A").unwrap();
+        let anchors = glyph.anchors().unwrap();
+        assert_eq!(anchors.len(), 2);
+        assert_eq!(anchors[0].name.as_deref(), Some("top"));
+        assert_eq!(anchors[0].color, Some(0xff));
+        assert_eq!(anchors[0].point(1), Some(Point { x: 101, y: 354 }));
+        let bottom = glyph.anchor("bottom").unwrap().unwrap();
+        assert_eq!(bottom.color, None);
+        assert_eq!(
+            bottom.points,
+            vec![Point { x: 100, y: 0 }, Point { x: 101, y: 0 }]
+        );
+    }
+
+    #[test]
+    fn test_anchors_count_mismatch() {
+        let vfb = vfb(&[0x8c, 0x8e, b't', b'o', b'p', 0x8a]);
+        assert!(vfb.glyph("A").unwrap().anchors().is_err());
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{entries::RawData, error::VfbError};
+use serde::Serialize;
+use std::{fs::File, io::Write};
+
+/// A pixel buffer with 1 or 8 bits per pixel. The rows are stored from top to bottom,
+/// each row padded to full bytes. With 1 bit per pixel, a set bit is a black pixel,
+/// with 8 bits per pixel, the value is the gray level from 0 (black) to 255 (white).
+#[derive(Serialize, Debug)]
+pub struct Bitmap {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub data: RawData,
+}
+
+impl Bitmap {
+    pub fn new(width: u32, height: u32, bit_depth: u8, data: Vec<u8>) -> Result<Self, VfbError> {
+        if bit_depth != 1 && bit_depth != 8 {
+            return Err(VfbError::BadValue(
+                format!("Bit depth {}", bit_depth),
+                "1 or 8".to_string(),
+            ));
+        }
+        let bitmap = Self {
+            width,
+            height,
+            bit_depth,
+            data: RawData(data),
+        };
+        let expected = bitmap.row_bytes() * height as usize;
+        if bitmap.data.0.len() < expected {
+            return Err(VfbError::BadValue(
+                format!("Bitmap data of {} bytes", bitmap.data.0.len()),
+                format!("{} bytes for {}x{} pixels", expected, width, height),
+            ));
+        }
+        Ok(bitmap)
+    }
+
+    /// The number of bytes per row
+    pub fn row_bytes(&self) -> usize {
+        (self.width as usize * self.bit_depth as usize).div_ceil(8)
+    }
+
+    /// The bytes of the given row
+    pub(crate) fn row(&self, y: u32) -> &[u8] {
+        let start = y as usize * self.row_bytes();
+        &self.data.0[start..start + self.row_bytes()]
+    }
+
+    /// Whether the pixel is black, or darker than 50% gray for 8-bit bitmaps
+    pub fn is_black(&self, x: u32, y: u32) -> bool {
+        if x >= self.width || y >= self.height {
+            return false;
+        }
+        let row = self.row(y);
+        match self.bit_depth {
+            1 => row[x as usize / 8] & (0x80 >> (x % 8)) != 0,
+            _ => row[x as usize] < 128,
+        }
+    }
+
+    /// Write the bitmap as a binary PBM (P4) image. 8-bit bitmaps are converted to
+    /// black and white.
+    pub fn write_pbm<W: Write>(&self, w: &mut W) -> Result<(), VfbError> {
+        let mut bytes = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        for y in 0..self.height {
+            if self.bit_depth == 1 {
+                bytes.extend(self.row(y));
+            } else {
+                let mut row = vec![0u8; self.width.div_ceil(8) as usize];
+                for x in 0..self.width {
+                    if self.is_black(x, y) {
+                        row[x as usize / 8] |= 0x80 >> (x % 8);
+                    }
+                }
+                bytes.extend(row);
+            }
+        }
+        w.write_all(&bytes).map_err(VfbError::WriteError)
+    }
+
+    /// Write the bitmap as a grayscale PNG image with the same bit depth
+    pub fn write_png<W: Write>(&self, w: &mut W) -> Result<(), VfbError> {
+        // Scanlines with filter type 0. PNG uses 0 for black, so 1-bit rows are inverted.
+        let mut scanlines = Vec::with_capacity((self.row_bytes() + 1) * self.height as usize);
+        for y in 0..self.height {
+            scanlines.push(0);
+            if self.bit_depth == 1 {
+                scanlines.extend(self.row(y).iter().map(|b| !b));
+            } else {
+                scanlines.extend(self.row(y));
+            }
+        }
+
+        let mut ihdr = vec![];
+        ihdr.extend(self.width.to_be_bytes());
+        ihdr.extend(self.height.to_be_bytes());
+        // Bit depth, color type grayscale, compression, filter, interlace
+        ihdr.extend([self.bit_depth, 0, 0, 0, 0]);
+
+        let mut bytes = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+        png_chunk(&mut bytes, b"IHDR", &ihdr);
+        png_chunk(&mut bytes, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut bytes, b"IEND", &[]);
+        w.write_all(&bytes).map_err(VfbError::WriteError)
+    }
+
+    /// Save the bitmap as a PBM file
+    pub fn save_pbm(&self, path: &str) -> Result<(), VfbError> {
+        let mut file = File::create(path).map_err(VfbError::FileCreateError)?;
+        self.write_pbm(&mut file)
+    }
+
+    /// Save the bitmap as a PNG file
+    pub fn save_png(&self, path: &str) -> Result<(), VfbError> {
+        let mut file = File::create(path).map_err(VfbError::FileCreateError)?;
+        self.write_png(&mut file)
+    }
+}
+
+/// Append a PNG chunk with its length and CRC
+fn png_chunk(bytes: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    bytes.extend((data.len() as u32).to_be_bytes());
+    let start = bytes.len();
+    bytes.extend(chunk_type);
+    bytes.extend(data);
+    let crc = crc32(&bytes[start..]);
+    bytes.extend(crc.to_be_bytes());
+}
+
+/// Wrap the data in a zlib stream of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut bytes = vec![0x78, 0x01];
+    let mut chunks = data.chunks(0xffff).peekable();
+    if chunks.peek().is_none() {
+        bytes.extend([0x01, 0x00, 0x00, 0xff, 0xff]);
+    }
+    while let Some(chunk) = chunks.next() {
+        let last = chunks.peek().is_none();
+        bytes.push(last as u8);
+        let len = chunk.len() as u16;
+        bytes.extend(len.to_le_bytes());
+        bytes.extend((!len).to_le_bytes());
+        bytes.extend(chunk);
+    }
+    bytes.extend(adler32(data).to_be_bytes());
+    bytes
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffffffffu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bitmap::{adler32, crc32, Bitmap};
+
+    /// A 3x2 bitmap with a black diagonal
+    fn bitmap() -> Bitmap {
+        Bitmap::new(3, 2, 1, vec![0b1000_0000, 0b0100_0000]).unwrap()
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae426082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e60398);
+    }
+
+    #[test]
+    fn test_bitmap_too_short() {
+        assert!(Bitmap::new(9, 2, 1, vec![0, 0, 0]).is_err());
+        assert!(Bitmap::new(1, 1, 4, vec![0]).is_err());
+    }
+
+    #[test]
+    fn test_is_black() {
+        let bitmap = bitmap();
+        assert!(bitmap.is_black(0, 0));
+        assert!(!bitmap.is_black(1, 0));
+        assert!(bitmap.is_black(1, 1));
+        assert!(!bitmap.is_black(3, 1));
+    }
+
+    #[test]
+    fn test_pbm() {
+        let mut bytes = vec![];
+        bitmap().write_pbm(&mut bytes).unwrap();
+        assert_eq!(bytes, b"P4\n3 2\n\x80\x40");
+    }
+
+    #[test]
+    fn test_pbm_8bit() {
+        let mut bytes = vec![];
+        let bitmap = Bitmap::new(2, 1, 8, vec![0x10, 0xf0]).unwrap();
+        bitmap.write_pbm(&mut bytes).unwrap();
+        assert_eq!(bytes, b"P4\n2 1\n\x80");
+    }
+
+    #[test]
+    fn test_png() {
+        let mut bytes = vec![];
+        bitmap().write_png(&mut bytes).unwrap();
+        assert_eq!(bytes[..8], [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]);
+        // IHDR
+        assert_eq!(
+            bytes[8..33],
+            [
+                0, 0, 0, 13, b'I', b'H', b'D', b'R', 0, 0, 0, 3, 0, 0, 0, 2, 1, 0, 0, 0, 0, 0xb5,
+                0x0f, 0x5b, 0xb7
+            ]
+        );
+        // IDAT: zlib header, one stored block with the inverted scanlines, adler32
+        assert_eq!(
+            bytes[33..60],
+            [
+                0, 0, 0, 15, b'I', b'D', b'A', b'T', 0x78, 0x01, 0x01, 0x04, 0x00, 0xfb, 0xff,
+                0x00, 0x7f, 0x00, 0xbf, 0x02, 0x40, 0x01, 0x3f, 0xe8, 0x1e, 0x3d, 0x1a
+            ]
+        );
+        assert_eq!(
+            bytes[60..],
+            [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]
+        );
+    }
+}
@@ -4,12 +4,13 @@ use std::{
     io::{prelude::*, BufReader},
 };
 
-use crate::error::VfbError;
+use crate::{entries::RawData, error::VfbError, glyph::Point};
 
 const VFB_UNICODE_STRINGS: bool = false;
 
 pub struct VfbReader<R> {
     reader: BufReader<R>,
+    master_count: u16,
 }
 impl<R> VfbReader<R>
 where
@@ -18,6 +19,7 @@ where
     pub fn new(reader: R) -> Self {
         VfbReader {
             reader: BufReader::new(reader),
+            master_count: 1,
         }
     }
 
@@ -25,6 +27,15 @@ where
         &mut self.reader
     }
 
+    /// The number of masters in the font, needed to decompile MM entries
+    pub fn master_count(&self) -> u16 {
+        self.master_count
+    }
+
+    pub fn set_master_count(&mut self, master_count: u16) {
+        self.master_count = master_count;
+    }
+
     /// Read the specified number of bytes from a buffer
     pub fn read_bytes(&mut self, bytes_to_read: u64) -> Result<Vec<u8>, VfbError> {
         let mut buf = vec![];
@@ -36,8 +47,7 @@ where
     /// Read the remaining bytes from a buffer
     pub fn read_bytes_remainder(&mut self) -> Result<Vec<u8>, VfbError> {
         let mut buf = vec![];
-        let mut chunk = self.reader().take(0xFFFF);
-        let _ = chunk.read_to_end(&mut buf);
+        self.reader().read_to_end(&mut buf)?;
         Ok(buf)
     }
 
@@ -108,6 +118,59 @@ where
         Ok(u32::from_le_bytes(buf))
     }
 
+    /// Read an i16 value from a buffer
+    pub fn read_i16(&mut self) -> Result<i16, VfbError> {
+        let mut buf = [0u8; 2];
+        self.reader().read_exact(&mut buf)?;
+        Ok(i16::from_le_bytes(buf))
+    }
+
+    /// Read an i32 value from a buffer
+    pub fn read_i32(&mut self) -> Result<i32, VfbError> {
+        let mut buf = [0u8; 4];
+        self.reader().read_exact(&mut buf)?;
+        Ok(i32::from_le_bytes(buf))
+    }
+
+    /// Read a little-endian f64 value from a buffer
+    pub fn read_f64(&mut self) -> Result<f64, VfbError> {
+        let mut buf = [0u8; 8];
+        self.reader().read_exact(&mut buf)?;
+        Ok(f64::from_le_bytes(buf))
+    }
+
+    /// Read an "encoded value" that must not be negative, e.g. a count or a length
+    pub fn read_count(&mut self) -> Result<u32, VfbError> {
+        let v = self.read_value()?;
+        v.try_into().map_err(|_| {
+            VfbError::BadValue(v.to_string(), "a count greater or equal to 0".to_string())
+        })
+    }
+
+    /// Read a point stored as two encoded values
+    pub(crate) fn read_point(&mut self) -> Result<Point, VfbError> {
+        let x = self.read_value()?;
+        let y = self.read_value()?;
+        Ok(Point { x, y })
+    }
+
+    /// Read a list of `n` encoded values, one for each master
+    pub(crate) fn read_master_values(&mut self, n: u16) -> Result<Vec<i32>, VfbError> {
+        (0..n).map(|_| self.read_value()).collect()
+    }
+
+    /// Read a string that is preceded by its length as an encoded value
+    pub(crate) fn read_counted_str(&mut self) -> Result<String, VfbError> {
+        let len = self.read_count()?;
+        self.read_str(len.into())
+    }
+
+    /// Read binary data that is preceded by its length as an encoded value
+    pub(crate) fn read_counted_bytes(&mut self) -> Result<RawData, VfbError> {
+        let len = self.read_count()?;
+        Ok(RawData(self.read_bytes(len.into())?))
+    }
+
     /// Read an "encoded value" from a buffer
     ///
     /// Lifted from the Type 1 font spec:
@@ -159,7 +222,7 @@ where
 }
 
 // TODO: Do we need this?
-/// Read n u8 values from a buffer
+// Read n u8 values from a buffer
 // fn read_n_u8<R>(&mut self, n: u8) -> u8
 // where
 //     R: std::io::Read,
@@ -294,6 +357,11 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_count_negative() {
+        assert!(get_reader(&[0x8a]).read_count().is_err());
+    }
+
     #[test]
     fn test_key_value_map() {
         assert_eq!(
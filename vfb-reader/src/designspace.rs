@@ -0,0 +1,488 @@
+use crate::{buffer::VfbReader, entries::VfbEntryType, error::VfbError, Vfb};
+use serde::Serialize;
+
+/// The maximum number of axes of a FontLab Studio 5 font
+pub const MAX_AXES: usize = 4;
+
+/// The number of axis mappings stored for each axis
+const MAPPINGS_PER_AXIS: usize = 10;
+
+/// The decompiled "Master Location" entry, which appears once for each master
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct MasterLocation {
+    /// The index of the master
+    pub index: u32,
+    /// The coordinate on each axis, from 0 to 1
+    pub coordinates: Vec<f64>,
+}
+
+/// The coordinate of a master on an axis
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct AxisCoordinate {
+    pub axis: String,
+    /// The coordinate from 0 to 1
+    pub value: f64,
+}
+
+/// A master of a multiple master font
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Master {
+    pub name: String,
+    pub location: Vec<AxisCoordinate>,
+}
+
+/// A point of a piecewise-linear mapping
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct Mapping {
+    pub input: f64,
+    pub output: f64,
+}
+
+/// A piecewise-linear mapping for an axis.
+///
+/// For "Axis Mappings", the input is the user coordinate and the output the design
+/// coordinate from 0 to 1. For "Anisotropic Interpolation Mappings", both are
+/// interpolation positions from 0 to 1000, the input for the instance and the output
+/// for this axis.
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct MappingTable {
+    pub axis: String,
+    /// The mappings, sorted by input
+    pub mappings: Vec<Mapping>,
+}
+
+impl MappingTable {
+    /// Map an input value to the output. Values outside of the mapped range are
+    /// clamped, an empty mapping is the identity.
+    pub fn map(&self, input: f64) -> f64 {
+        interpolate(
+            self.mappings.iter().map(|m| (m.input, m.output)).collect(),
+            input,
+        )
+    }
+
+    /// Map an output value back to the input, see `map`
+    pub fn unmap(&self, output: f64) -> f64 {
+        interpolate(
+            self.mappings.iter().map(|m| (m.output, m.input)).collect(),
+            output,
+        )
+    }
+}
+
+/// Piecewise-linear interpolation between the given points
+fn interpolate(mut points: Vec<(f64, f64)>, x: f64) -> f64 {
+    points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return x;
+    };
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+    for pair in points.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        if x <= x1 {
+            if x1 == x0 {
+                return y1;
+            }
+            return y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+        }
+    }
+    last.1
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_master_location(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let index = self.read_count()?;
+        // The number of axes is not known here, the coordinates fill the rest of the entry
+        let bytes = self.read_bytes_remainder()?;
+        if bytes.len() % 8 != 0 {
+            return Err(VfbError::BadValue(
+                format!("Master location coordinates of {} bytes", bytes.len()),
+                "a multiple of 8 bytes".to_string(),
+            ));
+        }
+        let coordinates = bytes
+            .chunks(8)
+            .map(|c| f64::from_le_bytes(c.try_into().unwrap_or_default()))
+            .collect();
+        Ok(Some(VfbEntryType::MasterLocation(MasterLocation {
+            index,
+            coordinates,
+        })))
+    }
+
+    /// Decompile the "Axis Mappings Count" entry, the number of mappings for each of
+    /// the 4 possible axes
+    pub fn decompile_axis_mappings_count(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let counts = (0..MAX_AXES)
+            .map(|_| self.read_u32())
+            .collect::<Result<Vec<u32>, VfbError>>()?;
+        Ok(Some(VfbEntryType::AxisMappingsCount(counts)))
+    }
+
+    /// Decompile the "Axis Mappings" entry, 10 mappings for each of the 4 possible axes
+    /// as pairs of f64. Only the number of mappings from "Axis Mappings Count" are used.
+    pub fn decompile_axis_mappings(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let mut mappings = vec![];
+        for _ in 0..MAX_AXES * MAPPINGS_PER_AXIS {
+            let input = self.read_f64()?;
+            let output = self.read_f64()?;
+            mappings.push(Mapping { input, output });
+        }
+        Ok(Some(VfbEntryType::AxisMappings(mappings)))
+    }
+
+    /// Decompile the "Anisotropic Interpolation Mappings" entry: the number of axes,
+    /// then for each axis the number of mappings and the mappings as pairs of encoded
+    /// values
+    pub fn decompile_anisotropic_mappings(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let num_axes = self.read_count()?;
+        let mut axes = vec![];
+        for _ in 0..num_axes {
+            let num_mappings = self.read_count()?;
+            let mut mappings = vec![];
+            for _ in 0..num_mappings {
+                let input = self.read_value()?.into();
+                let output = self.read_value()?.into();
+                mappings.push(Mapping { input, output });
+            }
+            axes.push(mappings);
+        }
+        Ok(Some(VfbEntryType::AnisotropicMappings(axes)))
+    }
+
+    pub fn decompile_weight_vector(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let weights = (0..self.master_count())
+            .map(|_| self.read_f64())
+            .collect::<Result<Vec<f64>, VfbError>>()?;
+        Ok(Some(VfbEntryType::WeightVector(weights)))
+    }
+}
+
+impl Vfb {
+    /// The values of all string entries with the given key that don't belong to a glyph
+    fn string_entries(&self, key: &str) -> Vec<&str> {
+        self.entries()
+            .iter()
+            .filter(|e| e.key == key)
+            .filter_map(|e| match &e.entry {
+                VfbEntryType::String(s) => Some(s.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The axis names from the "Axis Name" entries, checked against the "Axis Count"
+    pub fn axis_names(&self) -> Result<Vec<&str>, VfbError> {
+        let names = self.string_entries("Axis Name");
+        let count = self.uint16_entry("Axis Count").unwrap_or_default();
+        if names.len() != count.into() {
+            return Err(VfbError::BadValue(
+                format!("{} axis names", names.len()),
+                format!("one for each of the {} axes", count),
+            ));
+        }
+        Ok(names)
+    }
+
+    /// The user-to-design mapping of each axis, from "Axis Mappings" and "Axis Mappings
+    /// Count". Axes without mappings have an empty table.
+    pub fn axis_mappings(&self) -> Result<Vec<MappingTable>, VfbError> {
+        let axes = self.axis_names()?;
+        let counts = match self.entry("Axis Mappings Count").map(|e| &e.entry) {
+            Some(VfbEntryType::AxisMappingsCount(counts)) => counts.as_slice(),
+            _ => &[],
+        };
+        let mappings = match self.entry("Axis Mappings").map(|e| &e.entry) {
+            Some(VfbEntryType::AxisMappings(mappings)) => mappings.as_slice(),
+            _ => &[],
+        };
+        let mut tables = vec![];
+        for (i, axis) in axes.iter().enumerate() {
+            let count = counts.get(i).copied().unwrap_or_default() as usize;
+            let start = i * MAPPINGS_PER_AXIS;
+            if count > MAPPINGS_PER_AXIS || (count > 0 && mappings.len() < start + count) {
+                return Err(VfbError::BadValue(
+                    format!("{} mappings for axis {}", count, axis),
+                    format!("at most {} stored mappings", MAPPINGS_PER_AXIS),
+                ));
+            }
+            let mut table = mappings
+                .get(start..start + count)
+                .unwrap_or_default()
+                .to_vec();
+            table.sort_by(|a, b| a.input.total_cmp(&b.input));
+            tables.push(MappingTable {
+                axis: axis.to_string(),
+                mappings: table,
+            });
+        }
+        Ok(tables)
+    }
+
+    /// The anisotropic interpolation mapping of each axis. Axes without mappings have an
+    /// empty table.
+    pub fn anisotropic_mappings(&self) -> Result<Vec<MappingTable>, VfbError> {
+        let axes = self.axis_names()?;
+        let mappings = match self
+            .entry("Anisotropic Interpolation Mappings")
+            .map(|e| &e.entry)
+        {
+            Some(VfbEntryType::AnisotropicMappings(mappings)) => mappings.as_slice(),
+            _ => &[],
+        };
+        if mappings.len() > axes.len() {
+            return Err(VfbError::BadValue(
+                format!("Anisotropic mappings for {} axes", mappings.len()),
+                format!("at most {} axes", axes.len()),
+            ));
+        }
+        Ok(axes
+            .iter()
+            .enumerate()
+            .map(|(i, axis)| {
+                let mut table = mappings.get(i).cloned().unwrap_or_default();
+                table.sort_by(|a, b| a.input.total_cmp(&b.input));
+                MappingTable {
+                    axis: axis.to_string(),
+                    mappings: table,
+                }
+            })
+            .collect())
+    }
+
+    /// The weight of each master in the default instance, from "weight_vector"
+    pub fn weight_vector(&self) -> Option<&[f64]> {
+        match &self.entry("weight_vector")?.entry {
+            VfbEntryType::WeightVector(weights) => Some(weights),
+            _ => None,
+        }
+    }
+
+    /// The masters with their names from the "Master Name" entries and their locations
+    /// from the "Master Location" entries, on the axes from the "Axis Name" entries.
+    ///
+    /// It is an error if there isn't a name and a location for each master, or if a
+    /// location doesn't have a coordinate for each axis.
+    pub fn masters(&self) -> Result<Vec<Master>, VfbError> {
+        let master_count = usize::from(self.master_count());
+        let axes = self.axis_names()?;
+        let names = self.string_entries("Master Name");
+        let mut locations: Vec<&MasterLocation> = self
+            .entries()
+            .iter()
+            .filter_map(|e| match &e.entry {
+                VfbEntryType::MasterLocation(location) => Some(location),
+                _ => None,
+            })
+            .collect();
+        locations.sort_by_key(|l| l.index);
+        if names.len() != master_count || locations.len() != master_count {
+            return Err(VfbError::BadValue(
+                format!(
+                    "{} master names and {} master locations",
+                    names.len(),
+                    locations.len()
+                ),
+                format!("one of each for the {} masters", master_count),
+            ));
+        }
+
+        let mut masters = vec![];
+        for (i, (name, location)) in names.into_iter().zip(locations).enumerate() {
+            if location.index as usize != i || location.coordinates.len() != axes.len() {
+                return Err(VfbError::BadValue(
+                    format!(
+                        "Location of master {} with {} coordinates",
+                        location.index,
+                        location.coordinates.len()
+                    ),
+                    format!(
+                        "a master index below {} and {} axes",
+                        master_count,
+                        axes.len()
+                    ),
+                ));
+            }
+            let location = axes
+                .iter()
+                .zip(&location.coordinates)
+                .map(|(axis, value)| AxisCoordinate {
+                    axis: axis.to_string(),
+                    value: *value,
+                })
+                .collect();
+            masters.push(Master {
+                name: name.to_string(),
+                location,
+            });
+        }
+        Ok(masters)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        designspace::{Mapping, MappingTable},
+        tests::vfb_with,
+        Vfb,
+    };
+
+    fn f64_bytes(values: &[f64]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    fn location_data(index: u8, coordinates: &[f64]) -> Vec<u8> {
+        let mut bytes = vec![index + 0x8b];
+        bytes.extend(f64_bytes(coordinates));
+        bytes
+    }
+
+    pub(crate) fn master_location_data() -> Vec<u8> {
+        location_data(1, &[1.0, 0.5])
+    }
+
+    pub(crate) fn weight_vector_data() -> Vec<u8> {
+        f64_bytes(&[0.75, 0.25])
+    }
+
+    /// 2 mappings for the first axis and 3 for the second
+    pub(crate) fn axis_mappings_count_data() -> Vec<u8> {
+        [2u32, 3, 0, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect()
+    }
+
+    pub(crate) fn axis_mappings_data() -> Vec<u8> {
+        let mut values = vec![0.0; 80];
+        values[..4].copy_from_slice(&[100.0, 0.0, 900.0, 1.0]);
+        values[20..26].copy_from_slice(&[75.0, 0.0, 125.0, 1.0, 100.0, 0.8]);
+        f64_bytes(&values)
+    }
+
+    /// 1 axis with the mappings 0 -> 0, 500 -> 300 and 1000 -> 1000
+    pub(crate) fn anisotropic_mappings_data() -> Vec<u8> {
+        vec![
+            0x8c, 0x8e, 0x8b, 0x8b, 0xf8, 0x88, 0xf7, 0xc0, 0xfa, 0x7c, 0xfa, 0x7c,
+        ]
+    }
+
+    /// A VFB with 2 masters on the axes "Weight" and "Width"
+    fn vfb_with_axes(extra: &[(u16, Vec<u8>)]) -> Vfb {
+        let mut entries = vec![
+            (1503, vec![0x02, 0x00]),
+            (1517, weight_vector_data()),
+            (1513, vec![0x02, 0x00]),
+            (1514, b"Weight".to_vec()),
+            (1514, b"Width".to_vec()),
+        ];
+        entries.extend_from_slice(extra);
+        entries.extend([
+            (1504, b"Light".to_vec()),
+            (1505, location_data(0, &[0.0, 0.5])),
+            (1504, b"Bold".to_vec()),
+            (1505, master_location_data()),
+        ]);
+        vfb_with(&entries)
+    }
+
+    #[test]
+    fn test_masters() {
+        let vfb = vfb_with_axes(&[]);
+        assert_eq!(vfb.axis_names().unwrap(), vec!["Weight", "Width"]);
+        assert_eq!(vfb.weight_vector(), Some([0.75, 0.25].as_slice()));
+        let masters = vfb.masters().unwrap();
+        assert_eq!(masters.len(), 2);
+        assert_eq!(masters[0].name, "Light");
+        assert_eq!(masters[1].name, "Bold");
+        assert_eq!(masters[1].location[0].axis, "Weight");
+        assert_eq!(masters[1].location[0].value, 1.0);
+        assert_eq!(masters[1].location[1].axis, "Width");
+        assert_eq!(masters[1].location[1].value, 0.5);
+    }
+
+    #[test]
+    fn test_axis_mappings() {
+        let vfb = vfb_with_axes(&[
+            (1515, axis_mappings_count_data()),
+            (1516, axis_mappings_data()),
+        ]);
+        let tables = vfb.axis_mappings().unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].axis, "Weight");
+        assert_eq!(tables[0].mappings.len(), 2);
+        assert_eq!(tables[0].map(500.0), 0.5);
+        assert_eq!(tables[0].map(50.0), 0.0);
+        assert_eq!(tables[0].unmap(0.25), 300.0);
+        assert_eq!(
+            tables[1].mappings,
+            vec![
+                Mapping {
+                    input: 75.0,
+                    output: 0.0
+                },
+                Mapping {
+                    input: 100.0,
+                    output: 0.8
+                },
+                Mapping {
+                    input: 125.0,
+                    output: 1.0
+                }
+            ]
+        );
+        assert!((tables[1].map(87.5) - 0.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_axis_mappings_bad_count() {
+        let count = [11u32, 0, 0, 0]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        let vfb = vfb_with_axes(&[(1515, count), (1516, axis_mappings_data())]);
+        assert!(vfb.axis_mappings().is_err());
+    }
+
+    #[test]
+    fn test_anisotropic_mappings() {
+        let vfb = vfb_with_axes(&[(1523, anisotropic_mappings_data())]);
+        let tables = vfb.anisotropic_mappings().unwrap();
+        assert_eq!(tables[0].mappings.len(), 3);
+        assert_eq!(tables[0].map(250.0), 150.0);
+        assert!(tables[1].mappings.is_empty());
+        assert_eq!(tables[1].map(250.0), 250.0);
+        assert_eq!(vfb.axis_mappings().unwrap()[0].mappings, vec![]);
+    }
+
+    #[test]
+    fn test_empty_mapping_table() {
+        let table = MappingTable {
+            axis: "Weight".to_string(),
+            mappings: vec![],
+        };
+        assert_eq!(table.map(42.0), 42.0);
+        assert_eq!(table.unmap(42.0), 42.0);
+    }
+
+    #[test]
+    fn test_masters_axis_mismatch() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x01, 0x00]),
+            (1513, vec![0x01, 0x00]),
+            (1514, b"Weight".to_vec()),
+            (1504, b"Regular".to_vec()),
+            (1505, location_data(0, &[0.0, 0.5])),
+        ]);
+        assert!(vfb.masters().is_err());
+    }
+}
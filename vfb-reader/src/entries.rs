@@ -1,6 +1,25 @@
-use crate::{buffer::VfbReader, error::VfbError};
+use crate::{
+    anchors::AnchorProperties,
+    buffer::VfbReader,
+    designspace::{Mapping, MasterLocation},
+    error::VfbError,
+    gdef::GdefData,
+    glyph::{GlyphData, Point},
+    guides::{GuideProperty, Guides},
+    hinting::{HintingFlags, PostScriptHintingOptions},
+    image::BackgroundImage,
+    kerning::MMKernPair,
+    links::Links,
+    mask::Mask,
+    origin::{GlyphOrigin, Values2023},
+    postscript::PostScriptInfo,
+    sketch::Sketch,
+    strikes::GlyphBitmap,
+    writer::VfbWriter,
+};
 use serde::Serialize;
 
+#[derive(Debug)]
 pub struct RawData(pub Vec<u8>);
 
 impl Serialize for RawData {
@@ -18,6 +37,31 @@ pub enum VfbEntryType {
     Encoding((u16, String)),
     String(String),
     UInt16(u16),
+    Glyph(Box<GlyphData>),
+    MMKernPair(MMKernPair),
+    Links(Links),
+    Image(BackgroundImage),
+    GlyphBitmaps(Vec<GlyphBitmap>),
+    Sketch(Sketch),
+    Mask(Box<Mask>),
+    MaskMetrics(Point),
+    MaskMetricsMM(Vec<Point>),
+    Guides(Guides),
+    GuideProperties(Vec<GuideProperty>),
+    AnchorsSupplemental(Vec<AnchorProperties>),
+    AnchorsMM(Vec<Vec<Point>>),
+    GdefData(GdefData),
+    Unicodes(Vec<u32>),
+    HintingFlags(HintingFlags),
+    PostScriptHintingOptions(PostScriptHintingOptions),
+    GlyphOrigin(GlyphOrigin),
+    Values2023(Values2023),
+    PostScriptInfo(Box<PostScriptInfo>),
+    MasterLocation(MasterLocation),
+    WeightVector(Vec<f64>),
+    AxisMappingsCount(Vec<u32>),
+    AxisMappings(Vec<Mapping>),
+    AnisotropicMappings(Vec<Vec<Mapping>>),
 }
 
 impl<R> VfbReader<R>
@@ -43,20 +87,83 @@ where
     }
 }
 
+impl<W> VfbWriter<W>
+where
+    W: std::io::Write,
+{
+    pub fn compile_encoding(&mut self, gid: u16, name: &str) -> Result<(), VfbError> {
+        self.write_u16(gid)?;
+        self.write_str(name)
+    }
+
+    pub fn compile_string(&mut self, string: &str) -> Result<(), VfbError> {
+        self.write_str(string)
+    }
+
+    pub fn compile_uint16(&mut self, i: u16) -> Result<(), VfbError> {
+        self.write_u16(i)
+    }
+}
+
+/// Dispatch the compilation to the appropriate function, return None for entry types
+/// that can't be compiled
+pub fn compile(entry: &VfbEntryType) -> Result<Option<Vec<u8>>, VfbError> {
+    let mut w = VfbWriter::new(Vec::new());
+    match entry {
+        VfbEntryType::Raw(bytes) => w.write_bytes(&bytes.0)?,
+        VfbEntryType::Encoding((gid, name)) => w.compile_encoding(*gid, name)?,
+        VfbEntryType::String(string) => w.compile_string(string)?,
+        VfbEntryType::UInt16(i) => w.compile_uint16(*i)?,
+        _ => return Ok(None),
+    }
+    w.into_inner().map(Some)
+}
+
 /// Dispatch the decompilation to the appropriate function
-pub fn decompile(key: &str, bytes: &[u8]) -> Result<Option<VfbEntryType>, VfbError> {
+pub fn decompile(
+    key: &str,
+    bytes: &[u8],
+    master_count: u16,
+) -> Result<Option<VfbEntryType>, VfbError> {
     // The entry has data, but it is empty
     if bytes.is_empty() {
         return Ok(None);
     }
 
     let mut r = VfbReader::new(bytes);
+    r.set_master_count(master_count);
 
     // Match the entry key to the appropriate decompile function, return None for unknown keys
-    match key {
+    let decompiled = match key {
         "Encoding Default" => r.decompile_encoding(),
         "Encoding" => r.decompile_encoding(),
         "1502" => r.decompile_uint16(),
+        "Glyph" => r.decompile_glyph(),
+        "Links" => r.decompile_links(),
+        "image" => r.decompile_image(),
+        "Glyph Bitmaps" => r.decompile_glyph_bitmaps(),
+        "Glyph Sketch" => r.decompile_glyph_sketch(),
+        "mask" => r.decompile_mask(),
+        "mask.metrics" => r.decompile_mask_metrics(),
+        "mask.metrics_mm" => r.decompile_mask_metrics_mm(),
+        "Global Guides" => r.decompile_guides(),
+        "Global Guide Properties" | "Glyph Guide Properties" => r.decompile_guide_properties(),
+        "Global Mask" => r.decompile_mask(),
+        "Glyph Anchors Supplemental" => r.decompile_anchors_supplemental(),
+        "Glyph Anchors MM" => r.decompile_anchors_mm(),
+        "Glyph GDEF Data" => r.decompile_gdef_data(),
+        "unicodes" => r.decompile_unicodes(),
+        "Glyph Unicode Non-BMP" => r.decompile_unicodes_non_bmp(),
+        "Glyph Hinting Options" => r.decompile_glyph_hinting_options(),
+        "PostScript Hinting Options" => r.decompile_ps_hinting_options(),
+        "Glyph Origin" => r.decompile_glyph_origin(),
+        "2023" => r.decompile_values_2023(),
+        "PostScript Info" => r.decompile_postscript_info(),
+        "Master Location" => r.decompile_master_location(),
+        "weight_vector" => r.decompile_weight_vector(),
+        "Axis Mappings Count" => r.decompile_axis_mappings_count(),
+        "Axis Mappings" => r.decompile_axis_mappings(),
+        "Anisotropic Interpolation Mappings" => r.decompile_anisotropic_mappings(),
         "518" => r.decompile_string(),
         "257" => r.decompile_string(),
         "font_name" => r.decompile_string(),
@@ -121,6 +228,16 @@ pub fn decompile(key: &str, bytes: &[u8]) -> Result<Option<VfbEntryType>, VfbErr
         "mark" => r.decompile_uint16(),
         "glyph.customdata" => r.decompile_string(),
         "glyph.note" => r.decompile_string(),
+        "MM Kern Pair" => r.decompile_mm_kern_pair(),
         _ => Ok(None),
+    }?;
+    // Bytes that the decompiler didn't consume would be lost when compiling the entry
+    let trailing = r.read_bytes_remainder()?.len();
+    if decompiled.is_some() && trailing > 0 {
+        return Err(VfbError::BadValue(
+            format!("{} trailing bytes in entry {}", trailing, key),
+            "the decompiler to consume the whole entry".to_string(),
+        ));
     }
+    Ok(decompiled)
 }
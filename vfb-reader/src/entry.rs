@@ -1,8 +1,9 @@
 use crate::{
     buffer::VfbReader,
-    entries::{decompile, RawData, VfbEntryType},
+    entries::{compile, decompile, RawData, VfbEntryType},
     error::VfbError,
     vfb_constants,
+    writer::VfbWriter,
 };
 use serde::Serialize;
 use std::io::prelude::*;
@@ -10,36 +11,118 @@ use std::io::prelude::*;
 #[derive(Serialize)]
 pub struct VfbEntry {
     pub key: String,
-    pub entry: VfbEntryType,
+    /// The original binary data of a decompiled entry, used to write it back unchanged
+    #[serde(skip)]
+    pub(crate) data: Option<RawData>,
+    /// The structured data. Changes must go through `set_entry`, so `data` doesn't go
+    /// stale. Only fields derived from other entries, like resolved glyph names, are
+    /// updated in place.
+    pub(crate) entry: VfbEntryType,
+    /// Why the entry is kept as raw data, if it failed to decompile
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) decompile_error: Option<String>,
 }
 
 impl VfbEntry {
-    // Build the entry from binary data
-    pub fn new_from_data(key: String, data: Vec<u8>, decompile: bool) -> Result<Self, VfbError> {
+    // Build the entry from binary data. If the master count of the font is given, the
+    // entry is decompiled. Entries that fail to decompile are kept as raw data, so one
+    // unexpected entry doesn't make the whole file unreadable.
+    pub fn new_from_data(
+        key: String,
+        data: Vec<u8>,
+        master_count: Option<u16>,
+    ) -> Result<Self, VfbError> {
         let mut slf = Self {
             key,
+            data: None,
             entry: VfbEntryType::Raw(RawData(data)),
+            decompile_error: None,
         };
-        if decompile {
-            slf.decompile()?;
+        if let Some(master_count) = master_count {
+            if let Err(e) = slf.decompile(master_count) {
+                slf.decompile_error = Some(e.to_string());
+            }
         }
         Ok(slf)
     }
 
     // Build the entry from structured data
     pub fn new_from_decompiled(key: String, entry: VfbEntryType) -> Self {
-        Self { key, entry }
+        Self {
+            key,
+            data: None,
+            entry,
+            decompile_error: None,
+        }
     }
 
-    // Decompile the entry and store the result in the entry
-    pub fn decompile(&mut self) -> Result<(), VfbError> {
+    // Decompile the entry and store the result in the entry, keeping the original data.
+    // On error, the entry is left unchanged.
+    pub fn decompile(&mut self, master_count: u16) -> Result<(), VfbError> {
         if let VfbEntryType::Raw(bytes) = &self.entry {
-            if let Some(decompiled) = decompile(&self.key, &bytes.0)? {
-                self.entry = decompiled;
+            if let Some(decompiled) = decompile(&self.key, &bytes.0, master_count)? {
+                if let VfbEntryType::Raw(bytes) = std::mem::replace(&mut self.entry, decompiled) {
+                    self.data = Some(bytes);
+                }
             }
         }
         Ok(())
     }
+
+    // The structured data
+    pub fn entry(&self) -> &VfbEntryType {
+        &self.entry
+    }
+
+    // The original binary data of a decompiled entry, if it is unchanged
+    pub fn data(&self) -> Option<&RawData> {
+        self.data.as_ref()
+    }
+
+    // The error that kept the entry from being decompiled when it was read
+    pub fn decompile_error(&self) -> Option<&str> {
+        self.decompile_error.as_deref()
+    }
+
+    // Replace the structured data. The original data is discarded, because it no
+    // longer matches.
+    pub fn set_entry(&mut self, entry: VfbEntryType) {
+        self.entry = entry;
+        self.data = None;
+        self.decompile_error = None;
+    }
+
+    // Compile the entry to binary data. Entry types that can't be compiled yet fall
+    // back to the original data.
+    pub fn compile(&self) -> Result<Vec<u8>, VfbError> {
+        match compile(&self.entry)? {
+            Some(bytes) => Ok(bytes),
+            None => self
+                .data
+                .as_ref()
+                .map(|bytes| bytes.0.clone())
+                .ok_or_else(|| VfbError::Uncompilable(self.key.clone())),
+        }
+    }
+
+    // Return the original binary data if present, otherwise compile the entry
+    pub fn compile_roundtrip(&self) -> Result<Vec<u8>, VfbError> {
+        match &self.data {
+            Some(bytes) => Ok(bytes.0.clone()),
+            None => self.compile(),
+        }
+    }
+
+    /// Convert the human-readable key back to its numeric form using the VFB_KEYS
+    pub fn numeric_key(&self) -> Result<u16, VfbError> {
+        vfb_constants::VFB_KEYS
+            .entries()
+            .find(|(_, &v)| v == self.key)
+            .map(|(&k, _)| k)
+            .unwrap_or(self.key.as_str())
+            .parse()
+            .map_err(|_| VfbError::UnknownKey(self.key.clone()))
+    }
 }
 
 impl<R> VfbReader<R>
@@ -77,6 +160,47 @@ where
             });
 
         // Return the entry
-        VfbEntry::new_from_data(humankey, bytes, true)
+        let entry = VfbEntry::new_from_data(humankey, bytes, Some(self.master_count()))?;
+
+        // Entries following the master count depend on it. The count is not checked
+        // here, so files with an unexpected count can still be read; the outlines check
+        // it when they are decompiled.
+        if let ("Master Count", VfbEntryType::UInt16(master_count)) =
+            (entry.key.as_str(), &entry.entry)
+        {
+            self.set_master_count(*master_count);
+        }
+        Ok(entry)
+    }
+}
+
+impl<W> VfbWriter<W>
+where
+    W: std::io::Write,
+{
+    /// Write a VfbEntry to the stream
+    pub fn write_entry(&mut self, entry: &VfbEntry) -> Result<(), VfbError> {
+        let key = entry.numeric_key()?;
+        let bytes = if self.roundtrip() {
+            entry.compile_roundtrip()?
+        } else {
+            entry.compile()?
+        };
+        let size: u32 = bytes
+            .len()
+            .try_into()
+            .map_err(|_| VfbError::Overflow(u32::MAX))?;
+
+        // Data larger than u16::MAX needs a u32 size, which is indicated by masking the
+        // key with 0x8000
+        if size > u16::MAX.into() {
+            self.write_u16(key | 0x8000)?;
+            self.write_u32(size)?;
+        } else {
+            self.write_u16(key)?;
+            self.write_u16(size as u16)?;
+        }
+
+        self.write_bytes(&bytes)
     }
 }
@@ -4,8 +4,12 @@ use thiserror::Error;
 pub enum VfbError {
     #[error("Error opening file: {0}")]
     FileOpenError(std::io::Error),
+    #[error("Error creating file: {0}")]
+    FileCreateError(std::io::Error),
     #[error("Error reading from file: {0}")]
     ReadError(#[from] std::io::Error),
+    #[error("Error writing to file: {0}")]
+    WriteError(std::io::Error),
     #[error("Bad value: {0}, expected {1}")]
     BadValue(String, String),
     #[error("Value out of range: {0}")]
@@ -14,4 +18,16 @@ pub enum VfbError {
     InvalidUtf8(#[from] std::str::Utf8Error),
     #[error("Attempted to decompile an entry {0} that has no data")]
     UninitializedEntry(String),
+    #[error("Master {0} out of range, the font has {1} masters")]
+    MasterOutOfRange(usize, u16),
+    #[error("Glyph index {0} out of range")]
+    GlyphIndexOutOfRange(i64),
+    #[error("Glyph {0} couldn't be decompiled")]
+    UndecodableGlyph(usize),
+    #[error("Mismatched block marker: {0}")]
+    MismatchedMarker(String),
+    #[error("Entry {0} can't be compiled")]
+    Uncompilable(String),
+    #[error("Unknown entry key: {0}")]
+    UnknownKey(String),
 }
@@ -0,0 +1,103 @@
+use crate::{buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph_record::Glyph};
+use serde::Serialize;
+
+/// The GDEF glyph class
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum GlyphClass {
+    Base,
+    Ligature,
+    Mark,
+    Component,
+}
+
+impl GlyphClass {
+    /// The glyph class for the value stored in the VFB, which is the GDEF class value.
+    /// 0 means that no class is assigned.
+    fn from_value(value: i32) -> Result<Option<Self>, VfbError> {
+        match value {
+            0 => Ok(None),
+            1 => Ok(Some(GlyphClass::Base)),
+            2 => Ok(Some(GlyphClass::Ligature)),
+            3 => Ok(Some(GlyphClass::Mark)),
+            4 => Ok(Some(GlyphClass::Component)),
+            _ => Err(VfbError::BadValue(
+                format!("Glyph class {}", value),
+                "0 (unassigned), 1 (base), 2 (ligature), 3 (mark) or 4 (component)".to_string(),
+            )),
+        }
+    }
+}
+
+/// The decompiled "Glyph GDEF Data" entry
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GdefData {
+    pub class: Option<GlyphClass>,
+    /// The ligature carets with their position for each master
+    pub carets: Vec<Vec<i32>>,
+}
+
+impl GdefData {
+    /// The ligature caret positions of the given master
+    pub fn caret_positions(&self, master: usize) -> Vec<i32> {
+        self.carets
+            .iter()
+            .filter_map(|c| c.get(master).copied())
+            .collect()
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_gdef_data(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let class = GlyphClass::from_value(self.read_value()?)?;
+        let num_carets = self.read_count()?;
+        let carets = (0..num_carets)
+            .map(|_| self.read_master_values(self.master_count()))
+            .collect::<Result<Vec<_>, VfbError>>()?;
+        Ok(Some(VfbEntryType::GdefData(GdefData { class, carets })))
+    }
+}
+
+impl Glyph {
+    /// The GDEF glyph class and ligature carets of the glyph
+    pub fn gdef(&self) -> Option<&GdefData> {
+        match &self.entry("Glyph GDEF Data")?.entry {
+            VfbEntryType::GdefData(gdef) => Some(gdef),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{buffer::VfbReader, gdef::GlyphClass, glyph::tests::glyph_data, tests::vfb_with};
+
+    /// A ligature with 2 carets in 2 masters
+    pub(crate) fn gdef_data() -> Vec<u8> {
+        vec![0x8d, 0x8d, 0xef, 0xf0, 0xf7, 0x5c, 0xf7, 0x5d]
+    }
+
+    #[test]
+    fn test_gdef() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2018, gdef_data()),
+        ]);
+        let gdef = vfb.glyph("A").unwrap().gdef().unwrap();
+        assert_eq!(gdef.class, Some(GlyphClass::Ligature));
+        assert_eq!(gdef.carets, vec![vec![100, 101], vec![200, 201]]);
+        assert_eq!(gdef.caret_positions(1), vec![101, 201]);
+    }
+
+    #[test]
+    fn test_gdef_bad_class() {
+        let bytes = [0x90, 0x8b];
+        assert!(VfbReader::new(bytes.as_slice())
+            .decompile_gdef_data()
+            .is_err());
+    }
+}
@@ -0,0 +1,719 @@
+use crate::{
+    buffer::VfbReader,
+    entries::{RawData, VfbEntryType},
+    error::VfbError,
+    guides::Guides,
+    Vfb,
+};
+use serde::Serialize;
+
+/// The maximum number of masters in a FontLab Studio 5 font
+pub const MAX_MASTERS: u16 = 16;
+
+/// The maximum nesting depth of components, to catch circular references
+const MAX_COMPONENT_DEPTH: usize = 32;
+
+/// A point in font units
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    /// Add the coordinates of another point, failing if the result is out of range
+    pub fn checked_add(self, other: Point) -> Result<Point, VfbError> {
+        match (self.x.checked_add(other.x), self.y.checked_add(other.y)) {
+            (Some(x), Some(y)) => Ok(Point { x, y }),
+            _ => Err(VfbError::Overflow(u32::MAX)),
+        }
+    }
+}
+
+/// The type of an outline node, stored in the lower 4 bits of the node's first byte
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeType {
+    Move,
+    Line,
+    Curve,
+    QCurve,
+}
+
+impl NodeType {
+    fn from_u8(value: u8) -> Result<Self, VfbError> {
+        match value {
+            0 => Ok(NodeType::Move),
+            1 => Ok(NodeType::Line),
+            3 => Ok(NodeType::Curve),
+            4 => Ok(NodeType::QCurve),
+            _ => Err(VfbError::BadValue(
+                format!("Node type {}", value),
+                "0 (move), 1 (line), 3 (curve) or 4 (qcurve)".to_string(),
+            )),
+        }
+    }
+
+    /// The number of points per master stored for a node of this type. Curves store
+    /// the on-curve point followed by the two off-curve points.
+    pub fn num_points(&self) -> usize {
+        match self {
+            NodeType::Curve => 3,
+            _ => 1,
+        }
+    }
+}
+
+/// An outline node with its points for each master
+#[derive(Serialize, Debug)]
+pub struct Node {
+    pub node_type: NodeType,
+    pub smooth: bool,
+    /// The upper 4 bits of the node's first byte, including the smooth flag
+    pub flags: u8,
+    /// The points of the node, indexed by master, then by point
+    pub points: Vec<Vec<Point>>,
+}
+
+impl Node {
+    /// The points of the node for the given master
+    pub fn master_points(&self, master: usize) -> Option<&[Point]> {
+        self.points.get(master).map(|p| p.as_slice())
+    }
+}
+
+/// An outline node of a single master
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct MasterNode {
+    pub node_type: NodeType,
+    pub smooth: bool,
+    /// The on-curve point, followed by the off-curve points for curves
+    pub points: Vec<Point>,
+}
+
+impl MasterNode {
+    /// The on-curve point of the node
+    pub fn point(&self) -> Point {
+        self.points[0]
+    }
+}
+
+/// A contour of a single master, starting with a move node
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Contour {
+    pub nodes: Vec<MasterNode>,
+}
+
+/// A stem hint with its position and width for each master
+#[derive(Serialize, Debug)]
+pub struct Hint {
+    pub position: Vec<i32>,
+    pub width: Vec<i32>,
+}
+
+/// A hint replacement record. Hint replacement starts at the given node, the following
+/// records list the hints that are active from there.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HintReplacement {
+    /// The node index at which the following hints become active
+    Node(i32),
+    /// The index of a horizontal hint
+    H(i32),
+    /// The index of a vertical hint
+    V(i32),
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct Hints {
+    pub h: Vec<Hint>,
+    pub v: Vec<Hint>,
+    pub replacements: Vec<HintReplacement>,
+}
+
+/// A reference to another glyph with its offset and scale for each master
+#[derive(Serialize, Debug)]
+pub struct Component {
+    pub glyph_index: i32,
+    /// The name of the base glyph, resolved from the glyph order of the file
+    pub base_glyph: Option<String>,
+    pub offset_x: Vec<i32>,
+    pub offset_y: Vec<i32>,
+    pub scale_x: Vec<f64>,
+    pub scale_y: Vec<f64>,
+}
+
+impl Component {
+    /// Transform the contours of the base glyph for the given master
+    pub fn transform(&self, contours: &mut [Contour], master: usize) -> Result<(), VfbError> {
+        let (Some(dx), Some(dy), Some(sx), Some(sy)) = (
+            self.offset_x.get(master),
+            self.offset_y.get(master),
+            self.scale_x.get(master),
+            self.scale_y.get(master),
+        ) else {
+            return Err(VfbError::MasterOutOfRange(
+                master,
+                self.offset_x.len().try_into().unwrap_or(u16::MAX),
+            ));
+        };
+        for point in contours
+            .iter_mut()
+            .flat_map(|c| c.nodes.iter_mut())
+            .flat_map(|n| n.points.iter_mut())
+        {
+            // The float to int conversion saturates, only the offset can overflow
+            let scaled = Point {
+                x: (point.x as f64 * sx).round() as i32,
+                y: (point.y as f64 * sy).round() as i32,
+            };
+            *point = scaled.checked_add(Point { x: *dx, y: *dy })?;
+        }
+        Ok(())
+    }
+}
+
+/// A kerning pair with this glyph on the left side, and its value for each master
+#[derive(Serialize, Debug)]
+pub struct GlyphKerningPair {
+    pub glyph_index: i32,
+    /// The name of the right glyph, resolved from the glyph order of the file
+    pub right_glyph: Option<String>,
+    pub values: Vec<i32>,
+}
+
+/// The decompiled "Glyph" entry
+#[derive(Serialize, Debug, Default)]
+pub struct GlyphData {
+    /// Unknown values at the start of the entry
+    pub constants: Vec<u8>,
+    pub name: String,
+    /// The number of masters of the font, which is the number of coordinate sets in
+    /// each node
+    pub num_masters: u16,
+    pub nodes: Vec<Node>,
+    /// The advance width and height for each master
+    pub metrics: Vec<Point>,
+    pub hints: Hints,
+    pub guides: Guides,
+    pub components: Vec<Component>,
+    pub kerning: Vec<GlyphKerningPair>,
+    /// Imported binary data
+    pub binary: Option<RawData>,
+    /// TrueType instructions
+    pub instructions: Option<RawData>,
+}
+
+impl GlyphData {
+    /// The advance width of the given master
+    pub fn advance_width(&self, master: usize) -> Option<i32> {
+        self.metrics.get(master).map(|p| p.x)
+    }
+
+    /// The outline of the given master, split into contours
+    pub fn outline(&self, master: usize) -> Result<Vec<Contour>, VfbError> {
+        if master >= self.num_masters.into() {
+            return Err(VfbError::MasterOutOfRange(master, self.num_masters));
+        }
+        let mut contours: Vec<Contour> = vec![];
+        for node in &self.nodes {
+            if node.node_type == NodeType::Move || contours.is_empty() {
+                contours.push(Contour::default());
+            }
+            if let Some(contour) = contours.last_mut() {
+                contour.nodes.push(MasterNode {
+                    node_type: node.node_type,
+                    smooth: node.smooth,
+                    points: node.points[master].clone(),
+                });
+            }
+        }
+        Ok(contours)
+    }
+
+    /// The outlines of all masters, indexed by master
+    pub fn outlines(&self) -> Vec<Vec<Contour>> {
+        (0..self.num_masters.into())
+            .filter_map(|master| self.outline(master).ok())
+            .collect()
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    fn read_glyph_outlines(&mut self, glyph: &mut GlyphData) -> Result<(), VfbError> {
+        if self.master_count() == 0 || self.master_count() > MAX_MASTERS {
+            return Err(VfbError::BadValue(
+                format!("Master count {}", self.master_count()),
+                format!("between 1 and {}", MAX_MASTERS),
+            ));
+        }
+        let num_masters: u16 = self
+            .read_count()?
+            .try_into()
+            .map_err(|_| VfbError::Overflow(u32::MAX))?;
+        if num_masters != self.master_count() {
+            return Err(VfbError::BadValue(
+                format!("Outlines with {} masters", num_masters),
+                format!("the font's master count {}", self.master_count()),
+            ));
+        }
+        let num_nodes = self.read_count()?;
+
+        // The coordinates are stored relative to the previous point of the same master
+        let mut current = vec![Point::default(); num_masters.into()];
+        for _ in 0..num_nodes {
+            let byte = self.read_u8()?;
+            let node_type = NodeType::from_u8(byte & 0x0f)?;
+            let flags = byte >> 4;
+            let mut points = Vec::with_capacity(num_masters.into());
+            for pos in current.iter_mut() {
+                let mut master_points = Vec::with_capacity(node_type.num_points());
+                for _ in 0..node_type.num_points() {
+                    let x = self.read_value()?;
+                    let y = self.read_value()?;
+                    *pos = pos.checked_add(Point { x, y })?;
+                    master_points.push(*pos);
+                }
+                points.push(master_points);
+            }
+            glyph.nodes.push(Node {
+                node_type,
+                smooth: flags & 0x01 != 0,
+                flags,
+                points,
+            });
+        }
+        Ok(())
+    }
+
+    fn read_glyph_metrics(&mut self, glyph: &mut GlyphData) -> Result<(), VfbError> {
+        for _ in 0..self.master_count() {
+            let x = self.read_value()?;
+            let y = self.read_value()?;
+            glyph.metrics.push(Point { x, y });
+        }
+        Ok(())
+    }
+
+    fn read_hint_list(&mut self) -> Result<Vec<Hint>, VfbError> {
+        let num_hints = self.read_count()?;
+        let mut hints = vec![];
+        for _ in 0..num_hints {
+            let mut hint = Hint {
+                position: vec![],
+                width: vec![],
+            };
+            for _ in 0..self.master_count() {
+                hint.position.push(self.read_value()?);
+                hint.width.push(self.read_value()?);
+            }
+            hints.push(hint);
+        }
+        Ok(hints)
+    }
+
+    fn read_glyph_hints(&mut self, glyph: &mut GlyphData) -> Result<(), VfbError> {
+        glyph.hints.h = self.read_hint_list()?;
+        glyph.hints.v = self.read_hint_list()?;
+
+        let num_replacements = self.read_count()?;
+        for _ in 0..num_replacements {
+            let hint_type = self.read_u8()?;
+            let index = self.read_value()?;
+            glyph.hints.replacements.push(match hint_type {
+                0xff => HintReplacement::Node(index),
+                0x01 => HintReplacement::H(index),
+                0x02 => HintReplacement::V(index),
+                _ => {
+                    return Err(VfbError::BadValue(
+                        format!("Hint replacement type {}", hint_type),
+                        "1 (h), 2 (v) or 255 (node)".to_string(),
+                    ))
+                }
+            });
+        }
+        Ok(())
+    }
+
+    fn read_glyph_guides(&mut self, glyph: &mut GlyphData) -> Result<(), VfbError> {
+        glyph.guides = self.read_guides()?;
+        Ok(())
+    }
+
+    fn read_glyph_components(&mut self, glyph: &mut GlyphData) -> Result<(), VfbError> {
+        let num_components = self.read_count()?;
+        for _ in 0..num_components {
+            let mut component = Component {
+                glyph_index: self.read_value()?,
+                base_glyph: None,
+                offset_x: vec![],
+                offset_y: vec![],
+                scale_x: vec![],
+                scale_y: vec![],
+            };
+            for _ in 0..self.master_count() {
+                component.offset_x.push(self.read_value()?);
+                component.offset_y.push(self.read_value()?);
+                component.scale_x.push(self.read_f64()?);
+                component.scale_y.push(self.read_f64()?);
+            }
+            glyph.components.push(component);
+        }
+        Ok(())
+    }
+
+    fn read_glyph_kerning(&mut self, glyph: &mut GlyphData) -> Result<(), VfbError> {
+        let num_pairs = self.read_count()?;
+        for _ in 0..num_pairs {
+            let glyph_index = self.read_value()?;
+            let values = self.read_master_values(self.master_count())?;
+            glyph.kerning.push(GlyphKerningPair {
+                glyph_index,
+                right_glyph: None,
+                values,
+            });
+        }
+        Ok(())
+    }
+
+    /// Read the segments of a glyph until the end marker
+    pub(crate) fn read_glyph_segments(&mut self, glyph: &mut GlyphData) -> Result<(), VfbError> {
+        loop {
+            let segment = self.read_u8()?;
+            match segment {
+                0x01 => glyph.name = self.read_counted_str()?,
+                0x02 => self.read_glyph_metrics(glyph)?,
+                0x03 => self.read_glyph_hints(glyph)?,
+                0x04 => self.read_glyph_guides(glyph)?,
+                0x05 => self.read_glyph_components(glyph)?,
+                0x06 => self.read_glyph_kerning(glyph)?,
+                0x08 => self.read_glyph_outlines(glyph)?,
+                0x09 => glyph.binary = Some(self.read_counted_bytes()?),
+                0x0a => glyph.instructions = Some(self.read_counted_bytes()?),
+                0x0f => break,
+                _ => {
+                    return Err(VfbError::BadValue(
+                        format!("Glyph segment {}", segment),
+                        "a known glyph segment or 15 (end)".to_string(),
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn decompile_glyph(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let mut glyph = GlyphData {
+            constants: self.read_bytes(4)?,
+            num_masters: self.master_count(),
+            ..Default::default()
+        };
+        self.read_glyph_segments(&mut glyph)?;
+        Ok(Some(VfbEntryType::Glyph(Box::new(glyph))))
+    }
+}
+
+impl Vfb {
+    /// The outline of the given glyph and master, with all components decomposed into
+    /// plain contours
+    pub fn decompose(&self, glyph_index: usize, master: usize) -> Result<Vec<Contour>, VfbError> {
+        self.decompose_glyph(&self.glyph_data(), glyph_index, master, 0)
+    }
+
+    fn decompose_glyph(
+        &self,
+        glyphs: &[Option<&GlyphData>],
+        glyph_index: usize,
+        master: usize,
+        depth: usize,
+    ) -> Result<Vec<Contour>, VfbError> {
+        let glyph = glyphs
+            .get(glyph_index)
+            .ok_or(VfbError::GlyphIndexOutOfRange(glyph_index as i64))?
+            .ok_or(VfbError::UndecodableGlyph(glyph_index))?;
+        if depth > MAX_COMPONENT_DEPTH {
+            return Err(VfbError::BadValue(
+                format!("Components nested deeper than {}", MAX_COMPONENT_DEPTH),
+                "no circular component references".to_string(),
+            ));
+        }
+        let mut contours = glyph.outline(master)?;
+        for component in &glyph.components {
+            let base_index = usize::try_from(component.glyph_index)
+                .map_err(|_| VfbError::GlyphIndexOutOfRange(component.glyph_index.into()))?;
+            let mut base = self.decompose_glyph(glyphs, base_index, master, depth + 1)?;
+            component.transform(&mut base, master)?;
+            contours.extend(base);
+        }
+        Ok(contours)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        buffer::VfbReader,
+        entries::VfbEntryType,
+        error::VfbError,
+        glyph::{Component, Contour, GlyphData, HintReplacement, MasterNode, NodeType, Point},
+        tests::vfb_with,
+        writer::encode_value,
+        Vfb,
+    };
+
+    fn values(values: &[i32]) -> Vec<u8> {
+        values.iter().flat_map(|v| encode_value(*v)).collect()
+    }
+
+    /// A glyph "A" with 2 masters: a closed contour, metrics, hints and a component
+    pub(crate) fn glyph_data() -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x09, 0x01, 0x00];
+        // Name
+        bytes.extend([0x01, 0x8c, 0x41]);
+        // Outlines: 2 masters, 3 nodes
+        bytes.push(0x08);
+        bytes.extend(values(&[2, 3]));
+        bytes.push(0x00);
+        bytes.extend(values(&[10, 0, 20, 0]));
+        bytes.push(0x01);
+        bytes.extend(values(&[0, 100, 0, 200]));
+        bytes.push(0x13);
+        bytes.extend(values(&[100, 0, -50, 0, -50, -100]));
+        bytes.extend(values(&[200, 0, -100, 0, -100, -200]));
+        // Metrics
+        bytes.push(0x02);
+        bytes.extend(values(&[500, 0, 600, 0]));
+        // Hints: 1 h hint, 0 v hints, 2 replacements
+        bytes.push(0x03);
+        bytes.extend(values(&[1, 0, 20, 0, 30, 0, 2]));
+        bytes.push(0xff);
+        bytes.extend(values(&[0]));
+        bytes.push(0x01);
+        bytes.extend(values(&[0]));
+        // Components: glyph 1
+        bytes.push(0x05);
+        bytes.extend(values(&[1, 1]));
+        for (x, y, scale) in [(10, 20, 1.0f64), (15, 25, 0.5f64)] {
+            bytes.extend(values(&[x, y]));
+            bytes.extend(scale.to_le_bytes());
+            bytes.extend(scale.to_le_bytes());
+        }
+        // Kerning: glyph 1
+        bytes.push(0x06);
+        bytes.extend(values(&[1, 1, -50, -60]));
+        bytes.push(0x0f);
+        bytes
+    }
+
+    /// A glyph "B" with 2 masters and a single line
+    pub(crate) fn base_glyph_data() -> Vec<u8> {
+        let mut bytes = vec![0x01, 0x09, 0x01, 0x00];
+        bytes.extend([0x01, 0x8c, 0x42]);
+        bytes.push(0x08);
+        bytes.extend(values(&[2, 2]));
+        bytes.push(0x00);
+        bytes.extend(values(&[0, 0, 0, 0]));
+        bytes.push(0x01);
+        bytes.extend(values(&[100, 50, 200, 100]));
+        bytes.push(0x0f);
+        bytes
+    }
+
+    /// A VFB with 2 masters and the glyphs "A" and "B"
+    pub(crate) fn vfb() -> Vfb {
+        vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2001, base_glyph_data()),
+        ])
+    }
+
+    pub(crate) fn decompile(bytes: &[u8], master_count: u16) -> GlyphData {
+        let mut r = VfbReader::new(bytes);
+        r.set_master_count(master_count);
+        match r.decompile_glyph().unwrap() {
+            Some(VfbEntryType::Glyph(glyph)) => *glyph,
+            _ => panic!("Not a glyph"),
+        }
+    }
+
+    #[test]
+    fn test_glyph_name() {
+        let glyph = decompile(&glyph_data(), 2);
+        assert_eq!(glyph.constants, vec![0x01, 0x09, 0x01, 0x00]);
+        assert_eq!(glyph.name, "A");
+    }
+
+    #[test]
+    fn test_glyph_nodes() {
+        let glyph = decompile(&glyph_data(), 2);
+        assert_eq!(glyph.num_masters, 2);
+        assert_eq!(glyph.nodes.len(), 3);
+        assert_eq!(glyph.nodes[0].node_type, NodeType::Move);
+        assert_eq!(glyph.nodes[0].points[1], vec![Point { x: 20, y: 0 }]);
+        assert_eq!(glyph.nodes[1].node_type, NodeType::Line);
+        assert_eq!(glyph.nodes[1].points[0], vec![Point { x: 10, y: 100 }]);
+        assert_eq!(glyph.nodes[2].node_type, NodeType::Curve);
+        assert!(glyph.nodes[2].smooth);
+        assert_eq!(
+            glyph.nodes[2].points[0],
+            vec![
+                Point { x: 110, y: 100 },
+                Point { x: 60, y: 100 },
+                Point { x: 10, y: 0 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_glyph_metrics_hints_components() {
+        let glyph = decompile(&glyph_data(), 2);
+        assert_eq!(glyph.advance_width(1), Some(600));
+        assert_eq!(glyph.hints.h.len(), 1);
+        assert_eq!(glyph.hints.h[0].width, vec![20, 30]);
+        assert!(glyph.hints.v.is_empty());
+        assert_eq!(
+            glyph.hints.replacements,
+            vec![HintReplacement::Node(0), HintReplacement::H(0)]
+        );
+        assert_eq!(glyph.components.len(), 1);
+        assert_eq!(glyph.components[0].offset_y, vec![20, 25]);
+        assert_eq!(glyph.components[0].scale_x, vec![1.0, 0.5]);
+    }
+
+    #[test]
+    fn test_glyph_outline() {
+        let glyph = decompile(&glyph_data(), 2);
+        let outline = glyph.outline(1).unwrap();
+        assert_eq!(outline.len(), 1);
+        assert_eq!(outline[0].nodes.len(), 3);
+        assert_eq!(outline[0].nodes[0].point(), Point { x: 20, y: 0 });
+        assert_eq!(outline[0].nodes[1].point(), Point { x: 20, y: 200 });
+        assert_eq!(outline[0].nodes[2].point(), Point { x: 220, y: 200 });
+        assert_eq!(outline[0].nodes[2].points.len(), 3);
+    }
+
+    #[test]
+    fn test_glyph_outlines() {
+        let glyph = decompile(&glyph_data(), 2);
+        let outlines = glyph.outlines();
+        assert_eq!(outlines.len(), 2);
+        assert_eq!(outlines[0], glyph.outline(0).unwrap());
+        assert!(glyph.outline(2).is_err());
+    }
+
+    #[test]
+    fn test_glyph_coordinate_overflow() {
+        let mut bytes = vec![0x01, 0x09, 0x01, 0x00, 0x08];
+        bytes.extend(values(&[1, 2]));
+        for _ in 0..2 {
+            bytes.push(0x01);
+            bytes.extend(values(&[i32::MAX, 0]));
+        }
+        bytes.push(0x0f);
+        let mut r = VfbReader::new(bytes.as_slice());
+        assert!(r.decompile_glyph().is_err());
+    }
+
+    #[test]
+    fn test_glyph_master_count_out_of_range() {
+        for master_count in [0, 17] {
+            let mut bytes = vec![0x01, 0x09, 0x01, 0x00, 0x08];
+            bytes.extend(values(&[master_count.into(), 0]));
+            bytes.push(0x0f);
+            let mut r = VfbReader::new(bytes.as_slice());
+            r.set_master_count(master_count);
+            assert!(r.decompile_glyph().is_err());
+        }
+    }
+
+    #[test]
+    fn test_glyph_master_count_mismatch() {
+        let bytes = glyph_data();
+        let mut r = VfbReader::new(bytes.as_slice());
+        r.set_master_count(3);
+        assert!(r.decompile_glyph().is_err());
+    }
+
+    #[test]
+    fn test_component_base_glyph() {
+        let vfb = vfb();
+        assert_eq!(
+            vfb.glyph_order(),
+            vec![Some("A".to_string()), Some("B".to_string())]
+        );
+        let glyphs = vfb.glyph_data();
+        assert_eq!(
+            glyphs[0].unwrap().components[0].base_glyph.as_deref(),
+            Some("B")
+        );
+    }
+
+    #[test]
+    fn test_undecodable_glyph_keeps_indices() {
+        // A glyph with an unknown segment type, followed by "B" and "A"
+        let mut bad = glyph_data();
+        bad.insert(bad.len() - 1, 0x0e);
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, bad),
+            (2001, base_glyph_data()),
+            (2001, glyph_data()),
+        ]);
+        assert_eq!(
+            vfb.glyph_order(),
+            vec![None, Some("B".to_string()), Some("A".to_string())]
+        );
+        // The component of "A" refers to glyph index 1, which is "B"
+        let a = vfb.glyph("A").unwrap().data().unwrap();
+        assert_eq!(a.components[0].base_glyph.as_deref(), Some("B"));
+        assert_eq!(
+            vfb.decompose(2, 0).unwrap(),
+            self::vfb().decompose(0, 0).unwrap()
+        );
+        assert!(matches!(
+            vfb.decompose(0, 0),
+            Err(VfbError::UndecodableGlyph(0))
+        ));
+    }
+
+    #[test]
+    fn test_component_transform_overflow() {
+        let component = Component {
+            glyph_index: 0,
+            base_glyph: None,
+            offset_x: vec![i32::MAX],
+            offset_y: vec![0],
+            scale_x: vec![1.0],
+            scale_y: vec![1.0],
+        };
+        let mut contours = vec![Contour {
+            nodes: vec![MasterNode {
+                node_type: NodeType::Move,
+                smooth: false,
+                points: vec![Point { x: 1, y: 0 }],
+            }],
+        }];
+        assert!(component.transform(&mut contours, 0).is_err());
+    }
+
+    #[test]
+    fn test_decompose() {
+        let vfb = vfb();
+        let contours = vfb.decompose(0, 1).unwrap();
+        assert_eq!(contours.len(), 2);
+        assert_eq!(contours[1].nodes[0].point(), Point { x: 15, y: 25 });
+        assert_eq!(contours[1].nodes[1].point(), Point { x: 115, y: 75 });
+        assert_eq!(vfb.decompose(1, 0).unwrap().len(), 1);
+        assert!(vfb.decompose(2, 0).is_err());
+    }
+
+    #[test]
+    fn test_glyph_bad_segment() {
+        let mut r = VfbReader::new(&[0x01, 0x09, 0x01, 0x00, 0x0e][..]);
+        assert!(r.decompile_glyph().is_err());
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{
+    entries::VfbEntryType, entry::VfbEntry, glyph::GlyphData, vfb_constants::GLYPH_KEYS, Vfb,
+};
+use serde::Serialize;
+
+/// A glyph, built from a "Glyph" entry and the entries following it that belong to the
+/// same glyph
+#[derive(Serialize)]
+pub struct Glyph {
+    /// The "Glyph" entry, followed by the other entries of the glyph in file order
+    pub entries: Vec<VfbEntry>,
+    /// The number of non-glyph entries of the VFB that precede this glyph
+    #[serde(skip)]
+    pub(crate) position: usize,
+}
+
+impl Glyph {
+    pub(crate) fn new(entry: VfbEntry, position: usize) -> Self {
+        Self {
+            entries: vec![entry],
+            position,
+        }
+    }
+
+    /// The decompiled "Glyph" entry
+    pub fn data(&self) -> Option<&GlyphData> {
+        match &self.entries.first()?.entry {
+            VfbEntryType::Glyph(glyph) => Some(glyph),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn data_mut(&mut self) -> Option<&mut GlyphData> {
+        match &mut self.entries.first_mut()?.entry {
+            VfbEntryType::Glyph(glyph) => Some(glyph),
+            _ => None,
+        }
+    }
+
+    /// The glyph name
+    pub fn name(&self) -> Option<&str> {
+        self.data().map(|g| g.name.as_str())
+    }
+
+    /// The first entry of the glyph with the given key
+    pub fn entry(&self, key: &str) -> Option<&VfbEntry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    /// All entries of the glyph with the given key
+    pub fn entries_by_key<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a VfbEntry> {
+        self.entries.iter().filter(move |e| e.key == key)
+    }
+
+    /// The mark color
+    pub fn mark(&self) -> Option<u16> {
+        match &self.entry("mark")?.entry {
+            VfbEntryType::UInt16(mark) => Some(*mark),
+            _ => None,
+        }
+    }
+
+    /// The glyph note
+    pub fn note(&self) -> Option<&str> {
+        self.string_entry("glyph.note")
+    }
+
+    /// The glyph user data
+    pub fn customdata(&self) -> Option<&str> {
+        self.string_entry("glyph.customdata")
+    }
+
+    fn string_entry(&self, key: &str) -> Option<&str> {
+        match &self.entry(key)?.entry {
+            VfbEntryType::String(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl Vfb {
+    /// Add an entry at the end of the VFB. A "Glyph" entry starts a new glyph, and the
+    /// entries that belong to a glyph are added to the glyph that directly precedes
+    /// them. All other entries are added to the entries of the VFB.
+    pub fn push_entry(&mut self, entry: VfbEntry) {
+        let position = self.entries.len();
+        if entry.key == "Glyph" {
+            self.glyphs.push(Glyph::new(entry, position));
+            return;
+        }
+        if GLYPH_KEYS.contains(&entry.key.as_str()) {
+            if let Some(glyph) = self.glyphs.last_mut() {
+                if glyph.position == position {
+                    glyph.entries.push(entry);
+                    return;
+                }
+            }
+        }
+        self.entries.push(entry);
+    }
+
+    /// The glyphs in the order of the glyph indices
+    pub fn glyphs(&self) -> &[Glyph] {
+        &self.glyphs
+    }
+
+    pub fn glyphs_mut(&mut self) -> &mut [Glyph] {
+        &mut self.glyphs
+    }
+
+    /// The glyph with the given name
+    pub fn glyph(&self, name: &str) -> Option<&Glyph> {
+        self.glyphs.iter().find(|g| g.name() == Some(name))
+    }
+
+    /// All entries of the VFB, including the glyph entries, in file order
+    pub fn all_entries(&self) -> Vec<&VfbEntry> {
+        let mut entries = vec![];
+        let mut glyphs = self.glyphs.iter().peekable();
+        for position in 0..=self.entries.len() {
+            while let Some(glyph) = glyphs.next_if(|g| g.position <= position) {
+                entries.extend(glyph.entries.iter());
+            }
+            if let Some(entry) = self.entries.get(position) {
+                entries.push(entry);
+            }
+        }
+        entries
+    }
+
+    /// The decompiled "Glyph" entries. Their order defines the glyph indices, `None`
+    /// stands for a glyph whose "Glyph" entry couldn't be decompiled.
+    pub fn glyph_data(&self) -> Vec<Option<&GlyphData>> {
+        self.glyphs.iter().map(|g| g.data()).collect()
+    }
+
+    /// The glyph names in the order of the glyph indices, `None` if the "Glyph" entry
+    /// couldn't be decompiled
+    pub fn glyph_order(&self) -> Vec<Option<String>> {
+        self.glyphs
+            .iter()
+            .map(|g| g.name().map(str::to_string))
+            .collect()
+    }
+
+    /// Resolve the glyph indices in references to other glyphs to glyph names
+    pub(crate) fn resolve_glyph_references(&mut self) {
+        let glyph_order = self.glyph_order();
+        let name = |index: i32| -> Option<String> {
+            usize::try_from(index)
+                .ok()
+                .and_then(|i| glyph_order.get(i).cloned().flatten())
+        };
+        for glyph in self.glyphs.iter_mut().filter_map(|g| g.data_mut()) {
+            for component in &mut glyph.components {
+                component.base_glyph = name(component.glyph_index);
+            }
+            for pair in &mut glyph.kerning {
+                pair.right_glyph = name(pair.glyph_index);
+            }
+        }
+        for entry in self.entries.iter_mut() {
+            if let VfbEntryType::MMKernPair(pair) = &mut entry.entry {
+                pair.left_glyph = name(pair.left_index);
+                pair.right_glyph = name(pair.right_index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        glyph::tests::{base_glyph_data, glyph_data},
+        tests::vfb_with,
+    };
+
+    #[test]
+    fn test_glyphs() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2012, vec![0x05, 0x00]),
+            (2017, b"Note".to_vec()),
+            (2001, base_glyph_data()),
+            (1743, vec![0x00]),
+            (2012, vec![0x06, 0x00]),
+        ]);
+
+        assert_eq!(vfb.glyphs().len(), 2);
+        let a = vfb.glyph("A").unwrap();
+        assert_eq!(a.entries.len(), 3);
+        assert_eq!(a.mark(), Some(5));
+        assert_eq!(a.note(), Some("Note"));
+        let b = vfb.glyph("B").unwrap();
+        assert_eq!(b.entries.len(), 1);
+        assert_eq!(b.mark(), None);
+
+        // A glyph entry that doesn't follow a glyph stays at the font level
+        assert_eq!(vfb.entries().len(), 3);
+        let keys: Vec<&str> = vfb.all_entries().iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "Master Count",
+                "Glyph",
+                "mark",
+                "glyph.note",
+                "Glyph",
+                "OpenType Export Options",
+                "mark"
+            ]
+        );
+    }
+}
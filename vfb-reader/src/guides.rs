@@ -0,0 +1,343 @@
+use crate::{
+    buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph_record::Glyph, mask::Mask, Vfb,
+};
+use serde::Serialize;
+
+/// Convert a guide angle as stored in the VFB to degrees. The angle is stored as the
+/// tangent of the angle between the guide and its orientation, multiplied by 10000.
+pub fn angle_to_degrees(angle: i32) -> f64 {
+    (angle as f64 / 10000.0).atan().to_degrees()
+}
+
+/// A guide with its position and angle for each master
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Guide {
+    pub position: Vec<i32>,
+    /// The angle for each master, see `angle_to_degrees`
+    pub angle: Vec<i32>,
+    /// The name from the guide properties
+    pub name: Option<String>,
+    /// The color from the guide properties as 0x00BBGGRR
+    pub color: Option<u32>,
+}
+
+impl Guide {
+    /// The angle of the given master in degrees
+    pub fn angle_degrees(&self, master: usize) -> Option<f64> {
+        self.angle.get(master).map(|a| angle_to_degrees(*a))
+    }
+
+    /// Whether the guide has the same position and angle in all masters
+    pub fn is_constant(&self) -> bool {
+        self.position.windows(2).all(|w| w[0] == w[1])
+            && self.angle.windows(2).all(|w| w[0] == w[1])
+    }
+}
+
+/// A guide in the format of a UFO `guideline`
+#[derive(Serialize, Debug, PartialEq)]
+pub struct UfoGuideline {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub angle: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The color as "r,g,b,a" string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+/// Horizontal and vertical guides
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Guides {
+    pub h: Vec<Guide>,
+    pub v: Vec<Guide>,
+}
+
+impl Guides {
+    /// Set the names and colors of the guides from the guide properties
+    pub fn apply_properties(&mut self, properties: &[GuideProperty]) -> Result<(), VfbError> {
+        for property in properties {
+            let (guides, index) = if property.index > 0 {
+                (&mut self.h, property.index - 1)
+            } else {
+                (&mut self.v, -property.index - 1)
+            };
+            let guide = usize::try_from(index)
+                .ok()
+                .and_then(|i| guides.get_mut(i))
+                .ok_or_else(|| {
+                    VfbError::BadValue(
+                        format!("Properties for guide {}", property.index),
+                        "the index of an existing guide".to_string(),
+                    )
+                })?;
+            guide.name = property.name.clone().filter(|name| !name.is_empty());
+            guide.color = property.color;
+        }
+        Ok(())
+    }
+
+    /// The guides of the given master as UFO guidelines, horizontal guides first
+    pub fn ufo_guidelines(&self, master: usize) -> Vec<UfoGuideline> {
+        let mut guidelines = vec![];
+        for (guide, horizontal) in self
+            .h
+            .iter()
+            .map(|g| (g, true))
+            .chain(self.v.iter().map(|g| (g, false)))
+        {
+            let Some(position) = guide.position.get(master).copied() else {
+                continue;
+            };
+            let degrees = guide.angle_degrees(master).unwrap_or_default();
+            let (x, y, angle) = match (horizontal, degrees == 0.0) {
+                (true, true) => (None, Some(position), None),
+                (true, false) => (Some(0), Some(position), Some(degrees)),
+                (false, true) => (Some(position), None, None),
+                (false, false) => (Some(position), Some(0), Some(90.0 - degrees)),
+            };
+            guidelines.push(UfoGuideline {
+                x,
+                y,
+                angle,
+                name: guide.name.clone(),
+                color: guide.color.map(ufo_color),
+            });
+        }
+        guidelines
+    }
+}
+
+/// Format a 0x00BBGGRR color as UFO color string
+fn ufo_color(color: u32) -> String {
+    let component = |shift: u32| {
+        let value = ((color >> shift) & 0xff) as f64 / 255.0;
+        (value * 1000.0).round() / 1000.0
+    };
+    format!("{},{},{},1", component(0), component(8), component(16))
+}
+
+/// The name and color of a guide, from the "Global Guide Properties" or "Glyph Guide
+/// Properties" entries
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GuideProperty {
+    /// The 1-based index of the guide, positive for horizontal and negative for
+    /// vertical guides
+    pub index: i32,
+    /// The color as 0x00BBGGRR, or None for the default color
+    pub color: Option<u32>,
+    pub name: Option<String>,
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    fn read_guide_list(&mut self) -> Result<Vec<Guide>, VfbError> {
+        let num_guides = self.read_count()?;
+        let mut guides = vec![];
+        for _ in 0..num_guides {
+            let mut guide = Guide::default();
+            for _ in 0..self.master_count() {
+                guide.position.push(self.read_value()?);
+                guide.angle.push(self.read_value()?);
+            }
+            guides.push(guide);
+        }
+        Ok(guides)
+    }
+
+    /// Read the horizontal, then the vertical guides
+    pub(crate) fn read_guides(&mut self) -> Result<Guides, VfbError> {
+        let h = self.read_guide_list()?;
+        let v = self.read_guide_list()?;
+        Ok(Guides { h, v })
+    }
+
+    /// Read guide property records until the terminating 0 index
+    pub(crate) fn read_guide_properties(&mut self) -> Result<Vec<GuideProperty>, VfbError> {
+        let mut properties = vec![];
+        loop {
+            let index = self.read_value()?;
+            if index == 0 {
+                break;
+            }
+            let color = u32::try_from(self.read_value()?).ok();
+            let name = self.read_counted_str()?;
+            properties.push(GuideProperty {
+                index,
+                color,
+                name: Some(name).filter(|n| !n.is_empty()),
+            });
+        }
+        Ok(properties)
+    }
+
+    pub fn decompile_guides(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        Ok(Some(VfbEntryType::Guides(self.read_guides()?)))
+    }
+
+    pub fn decompile_guide_properties(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        Ok(Some(VfbEntryType::GuideProperties(
+            self.read_guide_properties()?,
+        )))
+    }
+}
+
+impl Vfb {
+    /// The global guides with the names and colors from the "Global Guide Properties"
+    pub fn guides(&self) -> Result<Guides, VfbError> {
+        let mut guides = match self.entry("Global Guides").map(|e| &e.entry) {
+            Some(VfbEntryType::Guides(guides)) => guides.clone(),
+            _ => Guides::default(),
+        };
+        if let Some(VfbEntryType::GuideProperties(properties)) =
+            self.entry("Global Guide Properties").map(|e| &e.entry)
+        {
+            guides.apply_properties(properties)?;
+        }
+        Ok(guides)
+    }
+
+    /// The global mask, a background outline shared by all glyphs
+    pub fn global_mask(&self) -> Option<&Mask> {
+        match &self.entry("Global Mask")?.entry {
+            VfbEntryType::Mask(mask) => Some(mask),
+            _ => None,
+        }
+    }
+}
+
+impl Glyph {
+    /// The guides of the glyph with the names and colors from the "Glyph Guide
+    /// Properties"
+    pub fn guides(&self) -> Result<Guides, VfbError> {
+        let mut guides = self.data().map(|g| g.guides.clone()).unwrap_or_default();
+        if let Some(VfbEntryType::GuideProperties(properties)) =
+            self.entry("Glyph Guide Properties").map(|e| &e.entry)
+        {
+            guides.apply_properties(properties)?;
+        }
+        Ok(guides)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        glyph::tests::glyph_data,
+        guides::{angle_to_degrees, UfoGuideline},
+        mask::tests::mask_data,
+        tests::vfb_with,
+        Vfb,
+    };
+
+    /// 1 horizontal guide at 500/510 and 1 vertical guide at 100 with 45° in 2 masters
+    pub(crate) fn guides_data() -> Vec<u8> {
+        vec![
+            0x8c, 0xf8, 0x88, 0x8b, 0xf8, 0x92, 0x8b, // h
+            0x8c, 0xef, 0xff, 0x00, 0x00, 0x27, 0x10, 0xef, 0xff, 0x00, 0x00, 0x27, 0x10, // v
+        ]
+    }
+
+    /// A name and a red color for the horizontal guide, a name for the vertical guide
+    pub(crate) fn guide_properties_data() -> Vec<u8> {
+        vec![
+            0x8c, 0xf7, 0x93, 0x8f, b'x', b'-', b'h', b't', // h
+            0x8a, 0x8a, 0x8d, b's', b't', // v
+            0x8b,
+        ]
+    }
+
+    fn vfb() -> Vfb {
+        vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (1294, guides_data()),
+            (1296, guide_properties_data()),
+            (1295, mask_data()),
+        ])
+    }
+
+    #[test]
+    fn test_glyph_guides() {
+        // Glyph "A" with a guides segment before the end marker
+        let mut glyph = glyph_data();
+        glyph.pop();
+        glyph.push(0x04);
+        glyph.extend(guides_data());
+        glyph.push(0x0f);
+
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph),
+            (2031, guide_properties_data()),
+        ]);
+
+        let guides = vfb.glyph("A").unwrap().guides().unwrap();
+        assert_eq!(guides.h[0].position, vec![500, 510]);
+        assert_eq!(guides.h[0].name.as_deref(), Some("x-ht"));
+        assert_eq!(guides.v[0].name.as_deref(), Some("st"));
+        // The decompiled glyph keeps the guides without properties
+        let data = vfb.glyph("A").unwrap().data().unwrap();
+        assert_eq!(data.guides.h[0].name, None);
+    }
+
+    #[test]
+    fn test_glyph_guide_properties_out_of_range() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2031, guide_properties_data()),
+        ]);
+        assert!(vfb.glyph("A").unwrap().guides().is_err());
+    }
+
+    #[test]
+    fn test_angle_to_degrees() {
+        assert_eq!(angle_to_degrees(0), 0.0);
+        assert!((angle_to_degrees(10000) - 45.0).abs() < 1e-9);
+        assert!((angle_to_degrees(-10000) + 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_global_guides() {
+        let guides = vfb().guides().unwrap();
+        assert_eq!(guides.h[0].position, vec![500, 510]);
+        assert!(!guides.h[0].is_constant());
+        assert_eq!(guides.h[0].name.as_deref(), Some("x-ht"));
+        assert_eq!(guides.h[0].color, Some(0xff));
+        assert_eq!(guides.v[0].angle, vec![10000, 10000]);
+        assert!(guides.v[0].is_constant());
+        assert_eq!(guides.v[0].name.as_deref(), Some("st"));
+        assert_eq!(guides.v[0].color, None);
+    }
+
+    #[test]
+    fn test_ufo_guidelines() {
+        let guidelines = vfb().guides().unwrap().ufo_guidelines(1);
+        assert_eq!(
+            guidelines[0],
+            UfoGuideline {
+                x: None,
+                y: Some(510),
+                angle: None,
+                name: Some("x-ht".to_string()),
+                color: Some("1,0,0,1".to_string()),
+            }
+        );
+        assert_eq!(guidelines[1].x, Some(100));
+        assert_eq!(guidelines[1].y, Some(0));
+        assert!((guidelines[1].angle.unwrap() - 45.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_global_mask() {
+        let vfb = vfb();
+        let mask = vfb.global_mask().unwrap();
+        assert_eq!(mask.glyph.nodes.len(), 2);
+    }
+}
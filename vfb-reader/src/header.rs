@@ -1,4 +1,4 @@
-use crate::{buffer::VfbReader, error::VfbError};
+use crate::{buffer::VfbReader, error::VfbError, writer::VfbWriter};
 
 use serde::Serialize;
 use std::collections::HashMap;
@@ -9,6 +9,13 @@ struct Chunk {
     data: Vec<u8>,
 }
 
+impl Chunk {
+    /// FL4+ headers mark the presence of the creator block at the end of chunk 1
+    fn has_creator(&self) -> bool {
+        self.data.ends_with(&[10, 0])
+    }
+}
+
 impl Serialize for Chunk {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -25,6 +32,7 @@ pub struct Header {
     filetype: String,
     header1: u16,
     chunk1: Chunk,
+    creator_size: u16,
     creator: HashMap<u8, i32>,
     end0: u8,
     end1: u8,
@@ -43,19 +51,17 @@ where
         let chunk1_size: u64 = self.read_u16()?.into();
         let res = self.read_bytes(chunk1_size)?;
         let chunk1 = Chunk { data: res };
-        let chunk1_usize: usize = chunk1_size.try_into().unwrap();
-        let last = chunk1.data.as_slice()[chunk1_usize - 1];
-        let last2 = chunk1.data.as_slice()[chunk1_usize - 2];
+        let creator_size: u16;
         let creator: HashMap<u8, i32>;
         let end0: u8;
         let end1: u8;
-        if [last2, last] == [10, 0] {
+        if chunk1.has_creator() {
             // FL4+ additions over FL3
 
             // The size of the creator chunk is specified in the header, but it contains a
             // key-value map that is terminated by a null byte. So it seems overspecified,
-            // and we don't really need the `creator_size`.
-            let _creator_size = self.read_u16();
+            // and we only keep the `creator_size` to be able to write it back.
+            creator_size = self.read_u16()?;
 
             // We could read `creator_size` bytes here, but then `buffer::read_key_value_map()`
             // would need to work on bytes, not on the buffered reader as it does now:
@@ -70,6 +76,7 @@ where
             end1 = self.read_u8()?;
         } else {
             // Older header format, upgrade it. We use a custom version, 5.3.0.1, here.
+            creator_size = 0;
             creator = HashMap::from([(1, 1), (2, 0x05030001), (3, 0)]);
             end0 = 6;
             end1 = 1;
@@ -82,6 +89,7 @@ where
             filetype,
             header1,
             chunk1,
+            creator_size,
             creator,
             end0,
             end1,
@@ -89,3 +97,32 @@ where
         })
     }
 }
+
+impl<W> VfbWriter<W>
+where
+    W: std::io::Write,
+{
+    /// Write the header to the buffered writer
+    pub fn write_header(&mut self, header: &Header) -> Result<(), VfbError> {
+        self.write_u8(header.header0)?;
+        self.write_str(&header.filetype)?;
+        self.write_u16(header.header1)?;
+        let chunk1_size: u16 = header
+            .chunk1
+            .data
+            .len()
+            .try_into()
+            .map_err(|_| VfbError::Overflow(header.chunk1.data.len() as u32))?;
+        self.write_u16(chunk1_size)?;
+        self.write_bytes(&header.chunk1.data)?;
+        // Headers upgraded from the older format are written back in their original
+        // form, because the creator block is only present if chunk 1 announces it.
+        if header.chunk1.has_creator() {
+            self.write_u16(header.creator_size)?;
+            self.write_key_value_map(&header.creator)?;
+            self.write_u8(header.end0)?;
+            self.write_u8(header.end1)?;
+        }
+        self.write_u16(header.end2)
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph_record::Glyph, Vfb};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Hinting flags, used for glyphs in "Glyph Hinting Options" and for the font in
+/// "PostScript Hinting Options"
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HintingFlags {
+    /// The flags as stored in the VFB, including unknown bits
+    pub bits: u32,
+    /// Hint replacement is enabled (bit 0)
+    pub hint_replacement: bool,
+    /// Horizontal stems are exported as hstem3 (bit 1)
+    pub h_stem3: bool,
+    /// Vertical stems are exported as vstem3 (bit 2)
+    pub v_stem3: bool,
+    /// The PostScript hints were generated by the autohinter (bit 3)
+    pub autohinted: bool,
+    /// The TrueType instructions were generated by the autohinter (bit 4)
+    pub tt_autohinted: bool,
+    /// No TrueType instructions are exported (bit 5)
+    pub tt_no_hinting: bool,
+}
+
+impl HintingFlags {
+    pub fn from_bits(bits: u32) -> Self {
+        let bit = |n: u32| bits & (1 << n) != 0;
+        Self {
+            bits,
+            hint_replacement: bit(0),
+            h_stem3: bit(1),
+            v_stem3: bit(2),
+            autohinted: bit(3),
+            tt_autohinted: bit(4),
+            tt_no_hinting: bit(5),
+        }
+    }
+}
+
+/// The decompiled "PostScript Hinting Options" entry, a key-value map. Key 1 holds the
+/// hinting flags of the font.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct PostScriptHintingOptions {
+    pub flags: HintingFlags,
+    /// The values of the other keys, which are not known
+    pub other: BTreeMap<u8, i32>,
+}
+
+/// How the hints of a glyph were made
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HintingStatus {
+    /// The glyph has no hints or hint links
+    Unhinted,
+    /// The hints were generated by the autohinter
+    Autohinted,
+    /// The hints or hint links were made by hand
+    Manual,
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    /// Read an encoded value as flags
+    fn read_flags(&mut self) -> Result<HintingFlags, VfbError> {
+        let value = self.read_value()?;
+        Ok(HintingFlags::from_bits(value as u32))
+    }
+
+    pub fn decompile_glyph_hinting_options(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        Ok(Some(VfbEntryType::HintingFlags(self.read_flags()?)))
+    }
+
+    pub fn decompile_ps_hinting_options(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let mut other: BTreeMap<u8, i32> = self.read_key_value_map()?.into_iter().collect();
+        let flags = HintingFlags::from_bits(other.remove(&1).unwrap_or_default() as u32);
+        Ok(Some(VfbEntryType::PostScriptHintingOptions(
+            PostScriptHintingOptions { flags, other },
+        )))
+    }
+}
+
+impl Glyph {
+    /// The hinting flags of the glyph
+    pub fn hinting_options(&self) -> Option<HintingFlags> {
+        match &self.entry("Glyph Hinting Options")?.entry {
+            VfbEntryType::HintingFlags(flags) => Some(*flags),
+            _ => None,
+        }
+    }
+
+    /// Whether the glyph is unhinted, autohinted or manually hinted
+    pub fn hinting_status(&self) -> HintingStatus {
+        let has_hints = self
+            .data()
+            .is_some_and(|g| !g.hints.h.is_empty() || !g.hints.v.is_empty())
+            || self
+                .links()
+                .is_some_and(|l| !l.h.is_empty() || !l.v.is_empty());
+        if !has_hints {
+            HintingStatus::Unhinted
+        } else if self.hinting_options().is_some_and(|f| f.autohinted) {
+            HintingStatus::Autohinted
+        } else {
+            HintingStatus::Manual
+        }
+    }
+}
+
+impl Vfb {
+    /// The PostScript hinting options of the font
+    pub fn ps_hinting_options(&self) -> Option<&PostScriptHintingOptions> {
+        match &self.entry("PostScript Hinting Options")?.entry {
+            VfbEntryType::PostScriptHintingOptions(options) => Some(options),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        glyph::tests::{base_glyph_data, glyph_data},
+        hinting::{HintingFlags, HintingStatus},
+        tests::vfb_with,
+    };
+
+    /// Hint replacement and autohinted
+    pub(crate) fn glyph_hinting_options_data() -> Vec<u8> {
+        vec![0x94]
+    }
+
+    /// Hint replacement and TrueType autohinting, and an unknown key 2
+    pub(crate) fn ps_hinting_options_data() -> Vec<u8> {
+        vec![0x01, 0x9c, 0x02, 0x8e, 0x00]
+    }
+
+    #[test]
+    fn test_flags() {
+        let flags = HintingFlags::from_bits(0x46);
+        assert!(flags.h_stem3);
+        assert!(flags.v_stem3);
+        assert!(!flags.hint_replacement);
+        assert_eq!(flags.bits, 0x46);
+    }
+
+    #[test]
+    fn test_hinting_options() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (1093, ps_hinting_options_data()),
+            (2001, glyph_data()),
+            (2010, glyph_hinting_options_data()),
+            (2001, base_glyph_data()),
+        ]);
+
+        let options = vfb.ps_hinting_options().unwrap();
+        assert!(options.flags.hint_replacement);
+        assert!(options.flags.tt_autohinted);
+        assert!(!options.flags.autohinted);
+        assert_eq!(options.other.get(&2), Some(&3));
+
+        let a = vfb.glyph("A").unwrap();
+        assert!(a.hinting_options().unwrap().autohinted);
+        assert_eq!(a.hinting_status(), HintingStatus::Autohinted);
+        assert_eq!(
+            vfb.glyph("B").unwrap().hinting_status(),
+            HintingStatus::Unhinted
+        );
+    }
+
+    #[test]
+    fn test_manual_hinting() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2010, vec![0x8c]),
+            (2001, base_glyph_data()),
+            (2008, vec![0x8c, 0x8c, 0x8b, 0x8b]),
+        ]);
+        assert_eq!(
+            vfb.glyph("A").unwrap().hinting_status(),
+            HintingStatus::Manual
+        );
+        assert_eq!(
+            vfb.glyph("B").unwrap().hinting_status(),
+            HintingStatus::Manual
+        );
+    }
+}
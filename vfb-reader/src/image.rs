@@ -0,0 +1,95 @@
+use crate::{
+    bitmap::Bitmap, buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph::Point,
+    glyph_record::Glyph,
+};
+use serde::Serialize;
+
+/// The decompiled "image" entry, the background bitmap of a glyph
+#[derive(Serialize, Debug)]
+pub struct BackgroundImage {
+    /// The position of the bottom left corner of the image in font units
+    pub origin: Point,
+    /// The size of the image in font units
+    pub size: Point,
+    pub bitmap: Bitmap,
+}
+
+impl BackgroundImage {
+    /// Save the bitmap as a PNG file
+    pub fn save_png(&self, path: &str) -> Result<(), VfbError> {
+        self.bitmap.save_png(path)
+    }
+
+    /// Save the bitmap as a PBM file
+    pub fn save_pbm(&self, path: &str) -> Result<(), VfbError> {
+        self.bitmap.save_pbm(path)
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_image(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let origin = self.read_point()?;
+        let size = self.read_point()?;
+        let width = self.read_count()?;
+        let height = self.read_count()?;
+        let bit_depth: u8 = self
+            .read_count()?
+            .try_into()
+            .map_err(|_| VfbError::BadValue("Bit depth".to_string(), "1 or 8".to_string()))?;
+        let len = self.read_count()?;
+        let data = self.read_bytes(len.into())?;
+        let bitmap = Bitmap::new(width, height, bit_depth, data)?;
+        Ok(Some(VfbEntryType::Image(BackgroundImage {
+            origin,
+            size,
+            bitmap,
+        })))
+    }
+}
+
+impl Glyph {
+    /// The background bitmap of the glyph
+    pub fn image(&self) -> Option<&BackgroundImage> {
+        match &self.entry("image")?.entry {
+            VfbEntryType::Image(image) => Some(image),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        glyph::{tests::glyph_data, Point},
+        tests::vfb_with,
+    };
+
+    /// A 3x2 pixel background image at (-10, -20) with a size of 300x200 units
+    pub(crate) fn image_data() -> Vec<u8> {
+        vec![
+            0x81, 0x77, 0xf7, 0xc0, 0xf7, 0x5c, 0x8e, 0x8d, 0x8c, 0x8d, 0x80, 0x40,
+        ]
+    }
+
+    #[test]
+    fn test_image() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2007, image_data()),
+        ]);
+        let image = vfb.glyph("A").unwrap().image().unwrap();
+        assert_eq!(image.origin, Point { x: -10, y: -20 });
+        assert_eq!(image.size, Point { x: 300, y: 200 });
+        assert_eq!(image.bitmap.width, 3);
+        assert_eq!(image.bitmap.height, 2);
+        assert_eq!(image.bitmap.bit_depth, 1);
+        assert!(image.bitmap.is_black(1, 1));
+        let mut pbm = vec![];
+        image.bitmap.write_pbm(&mut pbm).unwrap();
+        assert_eq!(pbm, b"P4\n3 2\n\x80\x40");
+    }
+}
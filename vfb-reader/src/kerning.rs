@@ -0,0 +1,150 @@
+use crate::{buffer::VfbReader, entries::VfbEntryType, error::VfbError, Vfb};
+use serde::Serialize;
+
+/// The decompiled "MM Kern Pair" entry of the MM kerning block
+#[derive(Serialize, Debug)]
+pub struct MMKernPair {
+    pub left_index: i32,
+    pub right_index: i32,
+    /// The names of the glyphs, resolved from the glyph order of the file
+    pub left_glyph: Option<String>,
+    pub right_glyph: Option<String>,
+    pub values: Vec<i32>,
+}
+
+/// A kerning pair with the glyph names resolved, and its value for each master
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct KerningPair {
+    pub left: String,
+    pub right: String,
+    pub values: Vec<i32>,
+}
+
+/// The pair kerning of the font
+#[derive(Serialize, Debug, Default)]
+pub struct KerningTable {
+    pub pairs: Vec<KerningPair>,
+}
+
+impl KerningTable {
+    /// The values of the pair for each master
+    pub fn get(&self, left: &str, right: &str) -> Option<&[i32]> {
+        self.pairs
+            .iter()
+            .find(|p| p.left == left && p.right == right)
+            .map(|p| p.values.as_slice())
+    }
+
+    /// The kerning of the given master as (left, right, value)
+    pub fn master(&self, master: usize) -> Vec<(&str, &str, i32)> {
+        self.pairs
+            .iter()
+            .filter_map(|p| {
+                p.values
+                    .get(master)
+                    .map(|v| (p.left.as_str(), p.right.as_str(), *v))
+            })
+            .collect()
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_mm_kern_pair(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let left_index = self.read_value()?;
+        let right_index = self.read_value()?;
+        let values = self.read_master_values(self.master_count())?;
+        Ok(Some(VfbEntryType::MMKernPair(MMKernPair {
+            left_index,
+            right_index,
+            left_glyph: None,
+            right_glyph: None,
+            values,
+        })))
+    }
+}
+
+impl Vfb {
+    /// The kerning table built from the kerning pairs stored in the "Glyph" entries of
+    /// the left glyphs
+    pub fn kerning(&self) -> Result<KerningTable, VfbError> {
+        let mut table = KerningTable::default();
+        for glyph in self.glyph_data().into_iter().flatten() {
+            for pair in &glyph.kerning {
+                let right = pair
+                    .right_glyph
+                    .clone()
+                    .ok_or_else(|| VfbError::GlyphIndexOutOfRange(pair.glyph_index.into()))?;
+                table.pairs.push(KerningPair {
+                    left: glyph.name.clone(),
+                    right,
+                    values: pair.values.clone(),
+                });
+            }
+        }
+        Ok(table)
+    }
+
+    /// The kerning table built from the "MM Kern Pair" entries of the MM kerning block
+    pub fn mm_kerning(&self) -> Result<KerningTable, VfbError> {
+        let mut table = KerningTable::default();
+        for entry in self.entries() {
+            if let VfbEntryType::MMKernPair(pair) = &entry.entry {
+                let (Some(left), Some(right)) = (&pair.left_glyph, &pair.right_glyph) else {
+                    let index = match pair.left_glyph {
+                        None => pair.left_index,
+                        Some(_) => pair.right_index,
+                    };
+                    return Err(VfbError::GlyphIndexOutOfRange(index.into()));
+                };
+                table.pairs.push(KerningPair {
+                    left: left.clone(),
+                    right: right.clone(),
+                    values: pair.values.clone(),
+                });
+            }
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        glyph::tests::vfb,
+        glyph::tests::{base_glyph_data, glyph_data},
+        tests::vfb_with,
+    };
+
+    /// MM kerning pair B, A with 2 masters
+    pub(crate) fn mm_kern_pair_data() -> Vec<u8> {
+        vec![0x8c, 0x8b, 0x20, 0xfb, 0x00]
+    }
+
+    #[test]
+    fn test_mm_kerning() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2001, base_glyph_data()),
+            (272, vec![]),
+            (1410, mm_kern_pair_data()),
+            (528, vec![]),
+        ]);
+        assert_eq!(vfb.entries()[1].key, "272");
+        assert_eq!(vfb.entries()[3].key, "528");
+        let kerning = vfb.mm_kerning().unwrap();
+        assert_eq!(kerning.get("B", "A"), Some([-107, -108].as_slice()));
+    }
+
+    #[test]
+    fn test_kerning() {
+        let kerning = vfb().kerning().unwrap();
+        assert_eq!(kerning.pairs.len(), 1);
+        assert_eq!(kerning.get("A", "B"), Some([-50, -60].as_slice()));
+        assert_eq!(kerning.get("B", "A"), None);
+        assert_eq!(kerning.master(1), vec![("A", "B", -60)]);
+    }
+}
@@ -1,38 +1,378 @@
+pub mod anchors;
+pub mod bitmap;
 mod buffer;
-mod entries;
+pub mod designspace;
+pub mod entries;
 pub mod entry;
-mod error;
+pub mod error;
+pub mod gdef;
+pub mod glyph;
+pub mod glyph_record;
+pub mod guides;
 pub mod header;
+pub mod hinting;
+pub mod image;
+pub mod kerning;
+pub mod links;
+pub mod mask;
+pub mod origin;
+pub mod postscript;
+pub mod sketch;
+pub mod strikes;
+pub mod tree;
+pub mod unicodes;
 mod vfb_constants;
+pub mod writer;
 
 use serde::Serialize;
 use std::fs::File;
 
-use crate::{buffer::VfbReader, error::VfbError};
+use crate::{buffer::VfbReader, error::VfbError, writer::VfbWriter};
 
 /// The main struct representing the VFB
 #[derive(Serialize)]
 pub struct Vfb {
     header: header::Header,
     entries: Vec<entry::VfbEntry>,
+    glyphs: Vec<glyph_record::Glyph>,
+}
+
+impl Vfb {
+    pub fn new(header: header::Header, entries: Vec<entry::VfbEntry>) -> Self {
+        let mut vfb = Self {
+            header,
+            entries: Vec::new(),
+            glyphs: Vec::new(),
+        };
+        for entry in entries {
+            vfb.push_entry(entry);
+        }
+        vfb.resolve_glyph_references();
+        vfb
+    }
+
+    pub fn header(&self) -> &header::Header {
+        &self.header
+    }
+
+    /// The entries that don't belong to a glyph, see `glyphs()` for the others
+    pub fn entries(&self) -> &[entry::VfbEntry] {
+        &self.entries
+    }
+
+    /// The entries that don't belong to a glyph. The glyphs keep their position
+    /// relative to these entries, so inserting or removing entries before a glyph
+    /// moves the glyph.
+    pub fn entries_mut(&mut self) -> &mut Vec<entry::VfbEntry> {
+        &mut self.entries
+    }
+
+    /// The first entry with the given key that doesn't belong to a glyph
+    pub fn entry(&self, key: &str) -> Option<&entry::VfbEntry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    /// The value of the first UInt16 entry with the given key that doesn't belong to a
+    /// glyph
+    pub(crate) fn uint16_entry(&self, key: &str) -> Option<u16> {
+        match &self.entry(key)?.entry {
+            entries::VfbEntryType::UInt16(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The number of masters from the "Master Count" entry, 1 if there is none
+    pub fn master_count(&self) -> u16 {
+        self.entries
+            .iter()
+            .find_map(|e| match (e.key.as_str(), &e.entry) {
+                ("Master Count", entries::VfbEntryType::UInt16(n)) => Some(*n),
+                _ => None,
+            })
+            .unwrap_or(1)
+    }
 }
 
 pub fn read_vfb(path: &str) -> Result<Vfb, VfbError> {
     let file = File::open(path).map_err(VfbError::FileOpenError)?;
     let mut r = VfbReader::new(file);
-    let header = r.read_header()?;
-    let mut vfb = Vfb {
-        header,
-        entries: Vec::new(),
+    r.read_vfb()
+}
+
+/// Write the VFB to a file. In round-trip mode, unmodified entries are written using
+/// their original binary data.
+pub fn write_vfb(path: &str, vfb: &Vfb, roundtrip: bool) -> Result<(), VfbError> {
+    let file = File::create(path).map_err(VfbError::FileCreateError)?;
+    let mut w = VfbWriter::new(file);
+    w.set_roundtrip(roundtrip);
+    w.write_vfb(vfb)?;
+    w.flush()
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    /// Read the header and all entries up to the end marker
+    pub fn read_vfb(&mut self) -> Result<Vfb, VfbError> {
+        let header = self.read_header()?;
+        let mut vfb = Vfb {
+            header,
+            entries: Vec::new(),
+            glyphs: Vec::new(),
+        };
+        let mut entry: entry::VfbEntry;
+        loop {
+            entry = self.read_entry()?;
+            if entry.key == "EOF" {
+                // End of file, don't include
+                break;
+            }
+            vfb.push_entry(entry);
+        }
+        vfb.resolve_glyph_references();
+        Ok(vfb)
+    }
+}
+
+impl<W> VfbWriter<W>
+where
+    W: std::io::Write,
+{
+    /// Write the header and all entries, followed by the end markers
+    pub fn write_vfb(&mut self, vfb: &Vfb) -> Result<(), VfbError> {
+        self.write_header(&vfb.header)?;
+        for entry in vfb.all_entries() {
+            self.write_entry(entry)?;
+        }
+        // End of block: Font ("EOF"), end of block: File
+        self.write_entry(&entry::VfbEntry::new_from_data(
+            "EOF".to_string(),
+            vec![],
+            None,
+        )?)?;
+        self.write_u16(2)?;
+        self.write_u16(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        anchors, buffer::VfbReader, designspace, entries, gdef, glyph, guides, hinting, image,
+        kerning, links, mask, origin, postscript, sketch, strikes, unicodes,
+        vfb_constants::VFB_KEYS, writer::VfbWriter, Vfb,
     };
-    let mut entry: entry::VfbEntry;
-    loop {
-        entry = r.read_entry()?;
-        if entry.key == "EOF" {
-            // End of file, don't include
-            break;
+
+    /// Build a FL5 header with the creator block
+    pub(crate) fn header_fl5() -> Vec<u8> {
+        let mut bytes = vec![0x1a];
+        bytes.extend(b"WLF10");
+        bytes.extend([0x03, 0x00]);
+        bytes.extend([0x05, 0x00, 0x01, 0x02, 0x03, 0x0a, 0x00]);
+        bytes.extend([0x0b, 0x00]);
+        bytes.extend([
+            0x01, 0x8c, 0x02, 0xff, 0x05, 0x02, 0x02, 0x80, 0x03, 0x8b, 0x00,
+        ]);
+        bytes.extend([0x06, 0x01, 0x00, 0x00]);
+        bytes
+    }
+
+    /// Build a FL3 header without the creator block
+    pub(crate) fn header_fl3() -> Vec<u8> {
+        let mut bytes = vec![0x1a];
+        bytes.extend(b"WLF10");
+        bytes.extend([0x03, 0x00]);
+        bytes.extend([0x03, 0x00, 0x01, 0x02, 0x03]);
+        bytes.extend([0x00, 0x00]);
+        bytes
+    }
+
+    /// Sample data for an entry, which must be decompilable for the given key
+    fn sample_data(key: &str) -> Vec<u8> {
+        match key {
+            "Encoding Default" | "Encoding" => vec![0x34, 0x00, 0x66, 0x6f, 0x75, 0x72],
+            "Master Count" => vec![0x02, 0x00],
+            "Glyph" => glyph::tests::glyph_data(),
+            "MM Kern Pair" => kerning::tests::mm_kern_pair_data(),
+            "Links" => links::tests::links_data(),
+            "image" => image::tests::image_data(),
+            "Glyph Bitmaps" => strikes::tests::glyph_bitmaps_data(),
+            "Glyph Sketch" => sketch::tests::sketch_data(),
+            "mask" | "Global Mask" => mask::tests::mask_data(),
+            "Global Guides" => guides::tests::guides_data(),
+            "Global Guide Properties" | "Glyph Guide Properties" => {
+                guides::tests::guide_properties_data()
+            }
+            "Glyph Anchors Supplemental" => anchors::tests::anchors_supplemental_data(),
+            "Glyph Anchors MM" => anchors::tests::anchors_mm_data(),
+            "Glyph GDEF Data" => gdef::tests::gdef_data(),
+            "unicodes" => unicodes::tests::unicodes_data(),
+            "Glyph Unicode Non-BMP" => unicodes::tests::unicodes_non_bmp_data(),
+            "Glyph Hinting Options" => hinting::tests::glyph_hinting_options_data(),
+            "PostScript Hinting Options" => hinting::tests::ps_hinting_options_data(),
+            "Glyph Origin" => origin::tests::glyph_origin_data(),
+            "2023" => origin::tests::values_2023_data(),
+            "PostScript Info" => postscript::tests::postscript_info_data(),
+            "Master Location" => designspace::tests::master_location_data(),
+            "weight_vector" => designspace::tests::weight_vector_data(),
+            "Axis Mappings Count" => designspace::tests::axis_mappings_count_data(),
+            "Axis Mappings" => designspace::tests::axis_mappings_data(),
+            "Anisotropic Interpolation Mappings" => designspace::tests::anisotropic_mappings_data(),
+            "mask.metrics" => mask::tests::mask_metrics_data(),
+            "mask.metrics_mm" => mask::tests::mask_metrics_mm_data(),
+            _ => vec![0x01, 0x8c, 0x02, 0xf7, 0x00, 0x80, 0xe4, 0x00],
+        }
+    }
+
+    pub(crate) fn entry_bytes(key: u16, data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![];
+        if data.len() > u16::MAX.into() {
+            bytes.extend((key | 0x8000).to_le_bytes());
+            bytes.extend((data.len() as u32).to_le_bytes());
+        } else {
+            bytes.extend(key.to_le_bytes());
+            bytes.extend((data.len() as u16).to_le_bytes());
+        }
+        bytes.extend(data);
+        bytes
+    }
+
+    /// The keys and data of an entry of every known kind
+    fn corpus_entries() -> Vec<(u16, Vec<u8>)> {
+        let mut entries: Vec<(u16, Vec<u8>)> = VFB_KEYS
+            .entries()
+            .filter(|(_, &v)| v != "EOF")
+            .map(|(&k, &v)| (k.parse().unwrap(), sample_data(v)))
+            .collect();
+        // The master count comes first like in real files, as other entries depend on it
+        entries.sort_by_key(|(k, _)| (*k != 1503, *k));
+        // Empty entry and an entry with a u32 size
+        entries.push((1026, vec![]));
+        entries.push((1276, vec![0x20; 0x12345]));
+        entries
+    }
+
+    /// Build a VFB that contains an entry of every known kind
+    fn corpus(header: Vec<u8>) -> Vec<u8> {
+        let mut bytes = header;
+        for (key, data) in corpus_entries() {
+            bytes.extend(entry_bytes(key, &data));
+        }
+        bytes.extend(entry_bytes(5, &[]));
+        bytes.extend(entry_bytes(2, &[]));
+        bytes
+    }
+
+    pub(crate) fn read(bytes: &[u8]) -> Vfb {
+        VfbReader::new(bytes).read_vfb().unwrap()
+    }
+
+    /// Build a FontLab Studio 5 VFB with the given entries, followed by the end markers
+    pub(crate) fn vfb_bytes(entries: &[(u16, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = header_fl5();
+        for (key, data) in entries {
+            bytes.extend(entry_bytes(*key, data));
         }
-        vfb.entries.push(entry);
+        bytes.extend(entry_bytes(5, &[]));
+        bytes.extend(entry_bytes(2, &[]));
+        bytes
+    }
+
+    /// Read a FontLab Studio 5 VFB with the given entries
+    pub(crate) fn vfb_with(entries: &[(u16, Vec<u8>)]) -> Vfb {
+        read(&vfb_bytes(entries))
+    }
+
+    fn write(vfb: &Vfb, roundtrip: bool) -> Vec<u8> {
+        let mut w = VfbWriter::new(Vec::new());
+        w.set_roundtrip(roundtrip);
+        w.write_vfb(vfb).unwrap();
+        w.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip_fl5() {
+        let bytes = corpus(header_fl5());
+        assert_eq!(write(&read(&bytes), true), bytes);
+    }
+
+    #[test]
+    fn test_roundtrip_fl3() {
+        let bytes = corpus(header_fl3());
+        assert_eq!(write(&read(&bytes), true), bytes);
+    }
+
+    #[test]
+    fn test_roundtrip_every_entry() {
+        let vfb = read(&corpus(header_fl5()));
+        let expected = corpus_entries();
+        assert_eq!(vfb.all_entries().len(), expected.len());
+        for (entry, (key, data)) in vfb.all_entries().iter().zip(expected) {
+            assert_eq!(entry.numeric_key().unwrap(), key);
+            assert_eq!(
+                entry.compile_roundtrip().unwrap(),
+                data,
+                "Entry {} differs",
+                entry.key
+            );
+        }
+    }
+
+    #[test]
+    fn test_undecodable_entry_stays_raw() {
+        // A glyph with an unknown segment type
+        let mut glyph = glyph::tests::glyph_data();
+        glyph.insert(glyph.len() - 1, 0x0e);
+        let bytes = vfb_bytes(&[(1503, vec![0x02, 0x00]), (2001, glyph.clone())]);
+        let vfb = read(&bytes);
+
+        let entry = &vfb.glyphs()[0].entries[0];
+        assert!(matches!(&entry.entry, entries::VfbEntryType::Raw(data) if data.0 == glyph));
+        assert!(entry.decompile_error().unwrap().contains("segment"));
+        assert_eq!(vfb.entries()[0].decompile_error(), None);
+        assert_eq!(write(&vfb, true), bytes);
+        assert_eq!(write(&vfb, false), bytes);
+    }
+
+    #[test]
+    fn test_master_count() {
+        assert_eq!(read(&corpus(header_fl5())).master_count(), 2);
+    }
+
+    #[test]
+    fn test_master_count_out_of_range() {
+        let bytes = vfb_bytes(&[
+            (1026, b"MyFont-Regular".to_vec()),
+            (1503, vec![0x11, 0x00]),
+            (2001, glyph::tests::base_glyph_data()),
+        ]);
+        let vfb = read(&bytes);
+        assert_eq!(vfb.master_count(), 17);
+        assert_eq!(vfb.entries().len(), 2);
+        // The outlines can't be decompiled with more than 16 masters
+        assert!(vfb.glyphs()[0].data().is_none());
+        assert_eq!(write(&vfb, true), bytes);
+    }
+
+    #[test]
+    fn test_set_entry() {
+        let mut vfb = vfb_with(&[(1026, b"MyFont-Regular".to_vec())]);
+        let entry = &mut vfb.entries_mut()[0];
+        assert!(entry.data().is_some());
+        entry.set_entry(entries::VfbEntryType::String("Other".to_string()));
+        assert!(entry.data().is_none());
+
+        assert_eq!(write(&vfb, true), vfb_bytes(&[(1026, b"Other".to_vec())]));
+    }
+
+    #[test]
+    fn test_compile_lossless_entries() {
+        let bytes = vfb_bytes(&[
+            (1500, sample_data("Encoding")),
+            (1026, b"MyFont-Regular".to_vec()),
+            (1503, vec![0x02, 0x00]),
+        ]);
+        assert_eq!(write(&read(&bytes), false), bytes);
     }
-    Ok(vfb)
 }
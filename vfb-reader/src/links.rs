@@ -0,0 +1,125 @@
+use crate::{
+    buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph::GlyphData,
+    glyph_record::Glyph,
+};
+use serde::Serialize;
+
+/// A link between two nodes of the glyph, which generates a stem hint. Negative node
+/// indices are used for ghost links (-1: top, -2: bottom).
+#[derive(Serialize, Debug, PartialEq, Eq)]
+pub struct Link {
+    pub node1: i32,
+    pub node2: i32,
+}
+
+/// The decompiled "Links" entry
+#[derive(Serialize, Debug, Default)]
+pub struct Links {
+    /// Links that generate horizontal stem hints (`Glyph.hlinks`)
+    pub h: Vec<Link>,
+    /// Links that generate vertical stem hints (`Glyph.vlinks`)
+    pub v: Vec<Link>,
+}
+
+impl Links {
+    /// Check that the nodes referenced by the links exist in the glyph
+    pub fn validate(&self, glyph: &GlyphData) -> Result<(), VfbError> {
+        let num_nodes = glyph.nodes.len();
+        for link in self.h.iter().chain(self.v.iter()) {
+            for index in [link.node1, link.node2] {
+                let valid = match usize::try_from(index) {
+                    Ok(i) => i < num_nodes,
+                    Err(_) => index >= -2,
+                };
+                if !valid {
+                    return Err(VfbError::BadValue(
+                        format!("Link to node {} in glyph {}", index, glyph.name),
+                        format!("a node index below {}, or -1 or -2", num_nodes),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    fn read_link_list(&mut self) -> Result<Vec<Link>, VfbError> {
+        let num_links = self.read_count()?;
+        let mut links = vec![];
+        for _ in 0..num_links {
+            let node1 = self.read_value()?;
+            let node2 = self.read_value()?;
+            links.push(Link { node1, node2 });
+        }
+        Ok(links)
+    }
+
+    pub fn decompile_links(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let h = self.read_link_list()?;
+        let v = self.read_link_list()?;
+        Ok(Some(VfbEntryType::Links(Links { h, v })))
+    }
+}
+
+impl Glyph {
+    /// The hint links of the glyph
+    pub fn links(&self) -> Option<&Links> {
+        match &self.entry("Links")?.entry {
+            VfbEntryType::Links(links) => Some(links),
+            _ => None,
+        }
+    }
+
+    /// Check that the nodes referenced by the hint links exist in the glyph
+    pub fn validate_links(&self) -> Result<(), VfbError> {
+        match (self.links(), self.data()) {
+            (Some(links), Some(glyph)) => links.validate(glyph),
+            (Some(_), None) => Err(VfbError::UninitializedEntry("Glyph".to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{glyph::tests::glyph_data, links::Link, tests::vfb_with, Vfb};
+
+    /// A horizontal link from node 0 to node 1, and a vertical ghost link
+    pub(crate) fn links_data() -> Vec<u8> {
+        vec![0x8c, 0x8b, 0x8c, 0x8c, 0x8c, 0x8a]
+    }
+
+    fn vfb(links: &[u8]) -> Vfb {
+        vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2008, links.to_vec()),
+        ])
+    }
+
+    #[test]
+    fn test_links() {
+        let vfb = vfb(&links_data());
+        let glyph = vfb.glyph("A").unwrap();
+        let links = glyph.links().unwrap();
+        assert_eq!(links.h, vec![Link { node1: 0, node2: 1 }]);
+        assert_eq!(
+            links.v,
+            vec![Link {
+                node1: 1,
+                node2: -1
+            }]
+        );
+        assert!(glyph.validate_links().is_ok());
+    }
+
+    #[test]
+    fn test_links_invalid_node() {
+        let vfb = vfb(&[0x8c, 0x8b, 0x8e, 0x8b]);
+        assert!(vfb.glyph("A").unwrap().validate_links().is_err());
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    buffer::VfbReader,
+    entries::VfbEntryType,
+    error::VfbError,
+    glyph::{Contour, GlyphData, Point},
+    glyph_record::Glyph,
+};
+use serde::Serialize;
+
+/// The decompiled "mask" entry, the background outline of a glyph. It uses the same
+/// segments as the "Glyph" entry, but usually only contains outlines.
+#[derive(Serialize, Debug)]
+pub struct Mask {
+    /// Unknown value at the start of the entry
+    pub reserved: i32,
+    pub glyph: GlyphData,
+}
+
+/// The mask of a glyph together with its metrics from the "mask.metrics" and
+/// "mask.metrics_mm" entries
+#[derive(Serialize, Debug)]
+pub struct MaskLayer<'a> {
+    pub mask: &'a Mask,
+    /// The advance width and height of the first master from "mask.metrics"
+    pub metrics: Option<Point>,
+    /// The advance width and height of the masters 2 to n from "mask.metrics_mm"
+    pub metrics_mm: Option<&'a [Point]>,
+}
+
+impl MaskLayer<'_> {
+    /// The outline of the given master, split into contours
+    pub fn outline(&self, master: usize) -> Result<Vec<Contour>, VfbError> {
+        self.mask.glyph.outline(master)
+    }
+
+    /// The advance width and height of the given master. The first master is stored in
+    /// "mask.metrics", the others in "mask.metrics_mm".
+    pub fn master_metrics(&self, master: usize) -> Option<Point> {
+        match master {
+            0 => self.metrics,
+            _ => self.metrics_mm?.get(master - 1).copied(),
+        }
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_mask(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let reserved = self.read_value()?;
+        let mut glyph = GlyphData {
+            num_masters: self.master_count(),
+            ..Default::default()
+        };
+        self.read_glyph_segments(&mut glyph)?;
+        Ok(Some(VfbEntryType::Mask(Box::new(Mask { reserved, glyph }))))
+    }
+
+    pub fn decompile_mask_metrics(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        Ok(Some(VfbEntryType::MaskMetrics(self.read_point()?)))
+    }
+
+    /// Decompile the "mask.metrics_mm" entry, which holds the metrics of the masters 2
+    /// to n. The first master is stored in "mask.metrics".
+    pub fn decompile_mask_metrics_mm(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let metrics = (1..self.master_count())
+            .map(|_| self.read_point())
+            .collect::<Result<Vec<Point>, VfbError>>()?;
+        Ok(Some(VfbEntryType::MaskMetricsMM(metrics)))
+    }
+}
+
+impl Glyph {
+    /// The mask of the glyph, the background layer with its own outline and metrics
+    pub fn mask(&self) -> Option<MaskLayer<'_>> {
+        let mask = match &self.entry("mask")?.entry {
+            VfbEntryType::Mask(mask) => mask,
+            _ => return None,
+        };
+        let metrics = match self.entry("mask.metrics").map(|e| &e.entry) {
+            Some(VfbEntryType::MaskMetrics(metrics)) => Some(*metrics),
+            _ => None,
+        };
+        let metrics_mm = match self.entry("mask.metrics_mm").map(|e| &e.entry) {
+            Some(VfbEntryType::MaskMetricsMM(metrics)) => Some(metrics.as_slice()),
+            _ => None,
+        };
+        Some(MaskLayer {
+            mask,
+            metrics,
+            metrics_mm,
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        glyph::{
+            tests::{base_glyph_data, glyph_data},
+            NodeType, Point,
+        },
+        tests::vfb_with,
+    };
+
+    /// A mask with the outlines of glyph "B"
+    pub(crate) fn mask_data() -> Vec<u8> {
+        let mut bytes = vec![0x8b];
+        bytes.extend(&base_glyph_data()[4..]);
+        bytes
+    }
+
+    pub(crate) fn mask_metrics_data() -> Vec<u8> {
+        vec![0xf8, 0x88, 0x8b]
+    }
+
+    /// The metrics of the second master
+    pub(crate) fn mask_metrics_mm_data() -> Vec<u8> {
+        vec![0xf8, 0xec, 0x8b]
+    }
+
+    #[test]
+    fn test_mask() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2009, mask_data()),
+            (2011, mask_metrics_data()),
+            (2001, base_glyph_data()),
+            (2009, mask_data()),
+            (2011, mask_metrics_data()),
+            (2028, mask_metrics_mm_data()),
+        ]);
+
+        let a = vfb.glyph("A").unwrap().mask().unwrap();
+        let outline = a.outline(1).unwrap();
+        assert_eq!(outline[0].nodes[1].node_type, NodeType::Line);
+        assert_eq!(outline[0].nodes[1].point(), Point { x: 200, y: 100 });
+        assert_eq!(a.master_metrics(0), Some(Point { x: 500, y: 0 }));
+        assert_eq!(a.master_metrics(1), None);
+
+        let b = vfb.glyph("B").unwrap().mask().unwrap();
+        assert_eq!(b.metrics_mm.unwrap().len(), 1);
+        assert_eq!(b.master_metrics(0), Some(Point { x: 500, y: 0 }));
+        assert_eq!(b.master_metrics(1), Some(Point { x: 600, y: 0 }));
+        assert_eq!(b.master_metrics(2), None);
+        assert!(vfb.glyph("B").unwrap().data().unwrap().metrics.is_empty());
+    }
+}
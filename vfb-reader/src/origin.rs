@@ -0,0 +1,88 @@
+use crate::{
+    buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph::Point, glyph_record::Glyph,
+};
+use serde::Serialize;
+
+/// The decompiled "Glyph Origin" entry, an offset of the outline coordinates (unconfirmed).
+/// It is one point for all masters; an entry with more data stays raw.
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GlyphOrigin {
+    pub offset: Point,
+}
+
+/// The decompiled "2023" entry, one value per master. FILEFORMAT.md labels it VSB
+/// (vertical side bearing), which is unconfirmed.
+#[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Values2023 {
+    pub values: Vec<i32>,
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_glyph_origin(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let offset = self.read_point()?;
+        Ok(Some(VfbEntryType::GlyphOrigin(GlyphOrigin { offset })))
+    }
+
+    pub fn decompile_values_2023(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let values = self.read_master_values(self.master_count())?;
+        Ok(Some(VfbEntryType::Values2023(Values2023 { values })))
+    }
+}
+
+impl Glyph {
+    /// The origin offset of the glyph, (0, 0) if it is not set
+    pub fn origin(&self) -> GlyphOrigin {
+        match self.entry("Glyph Origin").map(|e| &e.entry) {
+            Some(VfbEntryType::GlyphOrigin(origin)) => *origin,
+            _ => GlyphOrigin::default(),
+        }
+    }
+
+    /// The values of the "2023" entry
+    pub fn values_2023(&self) -> Option<&Values2023> {
+        match &self.entry("2023")?.entry {
+            VfbEntryType::Values2023(values) => Some(values),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        glyph::{
+            tests::{base_glyph_data, glyph_data},
+            Point,
+        },
+        tests::vfb_with,
+    };
+
+    pub(crate) fn glyph_origin_data() -> Vec<u8> {
+        vec![0x95, 0x81]
+    }
+
+    pub(crate) fn values_2023_data() -> Vec<u8> {
+        vec![0x95, 0xa9]
+    }
+
+    #[test]
+    fn test_origin() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2023, values_2023_data()),
+            (2027, glyph_origin_data()),
+            (2001, base_glyph_data()),
+        ]);
+
+        let a = vfb.glyph("A").unwrap();
+        assert_eq!(a.origin().offset, Point { x: 10, y: -10 });
+        assert_eq!(a.values_2023().unwrap().values, vec![10, 30]);
+        let b = vfb.glyph("B").unwrap();
+        assert_eq!(b.origin().offset, Point::default());
+        assert!(b.values_2023().is_none());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{buffer::VfbReader, entries::VfbEntryType, error::VfbError, Vfb};
+use serde::Serialize;
+
+/// The decompiled "PostScript Info" entry, which appears once for each master.
+///
+/// The hinting values are stored in arrays of a fixed size. Only the number of values
+/// given by the corresponding count entry, e.g. "blue_values_num", are used, see
+/// `Vfb::postscript_info`.
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct PostScriptInfo {
+    pub force_bold: bool,
+    pub blue_values: Vec<i32>,
+    pub other_blues: Vec<i32>,
+    pub family_blues: Vec<i32>,
+    pub family_other_blues: Vec<i32>,
+    pub blue_scale: f64,
+    pub blue_shift: i32,
+    pub blue_fuzz: i32,
+    pub std_hw: i32,
+    pub std_vw: i32,
+    pub stem_snap_h: Vec<i32>,
+    pub stem_snap_v: Vec<i32>,
+    /// The font bounding box as x min, y min, x max, y max
+    pub bounding_box: [i16; 4],
+    pub adv_width_min: i16,
+    pub adv_width_max: i16,
+    pub adv_width_avg: i16,
+    pub ascender: i16,
+    pub descender: i16,
+    pub x_height: i16,
+    pub cap_height: i16,
+}
+
+/// The number of values stored for each array, and the key of the entry with the
+/// number of values in use
+const ARRAYS: [(&str, usize); 6] = [
+    ("blue_values_num", 14),
+    ("other_blues_num", 10),
+    ("family_blues_num", 14),
+    ("family_other_blues_num", 10),
+    ("stem_snap_h_num", 12),
+    ("stem_snap_v_num", 12),
+];
+
+impl PostScriptInfo {
+    /// The arrays in the order of `ARRAYS`
+    fn arrays_mut(&mut self) -> [&mut Vec<i32>; 6] {
+        [
+            &mut self.blue_values,
+            &mut self.other_blues,
+            &mut self.family_blues,
+            &mut self.family_other_blues,
+            &mut self.stem_snap_h,
+            &mut self.stem_snap_v,
+        ]
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    fn read_i32_array(&mut self, n: usize) -> Result<Vec<i32>, VfbError> {
+        (0..n).map(|_| self.read_i32()).collect()
+    }
+
+    pub fn decompile_postscript_info(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let mut info = PostScriptInfo {
+            force_bold: self.read_i32()? != 0,
+            ..Default::default()
+        };
+        info.blue_values = self.read_i32_array(ARRAYS[0].1)?;
+        info.other_blues = self.read_i32_array(ARRAYS[1].1)?;
+        info.family_blues = self.read_i32_array(ARRAYS[2].1)?;
+        info.family_other_blues = self.read_i32_array(ARRAYS[3].1)?;
+        info.blue_scale = self.read_f64()?;
+        info.blue_shift = self.read_i32()?;
+        info.blue_fuzz = self.read_i32()?;
+        info.std_hw = self.read_i32()?;
+        info.std_vw = self.read_i32()?;
+        info.stem_snap_h = self.read_i32_array(ARRAYS[4].1)?;
+        info.stem_snap_v = self.read_i32_array(ARRAYS[5].1)?;
+        for value in info.bounding_box.iter_mut() {
+            *value = self.read_i16()?;
+        }
+        info.adv_width_min = self.read_i16()?;
+        info.adv_width_max = self.read_i16()?;
+        info.adv_width_avg = self.read_i16()?;
+        info.ascender = self.read_i16()?;
+        info.descender = self.read_i16()?;
+        info.x_height = self.read_i16()?;
+        info.cap_height = self.read_i16()?;
+        Ok(Some(VfbEntryType::PostScriptInfo(Box::new(info))))
+    }
+}
+
+impl Vfb {
+    /// The PostScript info for each master. The arrays are cut to the number of values
+    /// from the count entries, e.g. "blue_values_num". If a count entry is missing, all
+    /// stored values are kept.
+    ///
+    /// It is an error if there isn't exactly one "PostScript Info" entry per master, or
+    /// if a count is larger than the number of stored values.
+    pub fn postscript_info(&self) -> Result<Vec<PostScriptInfo>, VfbError> {
+        let mut infos: Vec<PostScriptInfo> = self
+            .entries()
+            .iter()
+            .filter_map(|e| match &e.entry {
+                VfbEntryType::PostScriptInfo(info) => Some(info.as_ref().clone()),
+                _ => None,
+            })
+            .collect();
+        if infos.len() != self.master_count().into() {
+            return Err(VfbError::BadValue(
+                format!("{} PostScript Info entries", infos.len()),
+                format!("one for each of the {} masters", self.master_count()),
+            ));
+        }
+        for (key, size) in ARRAYS {
+            let Some(count) = self.uint16_entry(key) else {
+                continue;
+            };
+            if usize::from(count) > size {
+                return Err(VfbError::BadValue(
+                    format!("{} {}", key, count),
+                    format!("at most {}", size),
+                ));
+            }
+        }
+        for info in infos.iter_mut() {
+            for ((key, _), array) in ARRAYS.iter().zip(info.arrays_mut()) {
+                if let Some(count) = self.uint16_entry(key) {
+                    array.truncate(count.into());
+                }
+            }
+        }
+        Ok(infos)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{tests::vfb_with, Vfb};
+
+    fn i32_array(values: &[i32], size: usize) -> Vec<u8> {
+        let mut bytes: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+        bytes.resize(size * 4, 0);
+        bytes
+    }
+
+    /// PostScript info with all values offset by `offset`
+    pub(crate) fn postscript_info_data_offset(offset: i32) -> Vec<u8> {
+        let mut bytes = 1i32.to_le_bytes().to_vec();
+        bytes.extend(i32_array(&[-15, 0, 500 + offset, 515 + offset], 14));
+        bytes.extend(i32_array(&[-250, -240], 10));
+        bytes.extend(i32_array(&[-15, 0], 14));
+        bytes.extend(i32_array(&[], 10));
+        bytes.extend(0.039625f64.to_le_bytes());
+        for value in [7, 1, 80 + offset, 90 + offset] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.extend(i32_array(&[80 + offset], 12));
+        bytes.extend(i32_array(&[90 + offset, 100 + offset], 12));
+        for value in [-50, -250, 1000, 900, 200, 1000, 550, 750, -250, 500, 700] {
+            bytes.extend((value as i16).to_le_bytes());
+        }
+        bytes
+    }
+
+    pub(crate) fn postscript_info_data() -> Vec<u8> {
+        postscript_info_data_offset(0)
+    }
+
+    fn vfb(counts: &[(u16, u16)]) -> Vfb {
+        let mut entries = vec![(1503, vec![0x02, 0x00])];
+        for (key, count) in counts {
+            entries.push((*key, count.to_le_bytes().to_vec()));
+        }
+        entries.push((1536, postscript_info_data_offset(0)));
+        entries.push((1536, postscript_info_data_offset(20)));
+        vfb_with(&entries)
+    }
+
+    #[test]
+    fn test_postscript_info() {
+        let vfb = vfb(&[
+            (1530, 4),
+            (1531, 2),
+            (1532, 2),
+            (1533, 0),
+            (1534, 1),
+            (1535, 2),
+        ]);
+        let infos = vfb.postscript_info().unwrap();
+        assert_eq!(infos.len(), 2);
+        assert!(infos[0].force_bold);
+        assert_eq!(infos[0].blue_values, vec![-15, 0, 500, 515]);
+        assert_eq!(infos[1].blue_values, vec![-15, 0, 520, 535]);
+        assert_eq!(infos[1].other_blues, vec![-250, -240]);
+        assert!(infos[1].family_other_blues.is_empty());
+        assert_eq!(infos[1].blue_scale, 0.039625);
+        assert_eq!(infos[1].blue_shift, 7);
+        assert_eq!(infos[1].std_vw, 110);
+        assert_eq!(infos[1].stem_snap_h, vec![100]);
+        assert_eq!(infos[1].stem_snap_v, vec![110, 120]);
+        assert_eq!(infos[1].bounding_box, [-50, -250, 1000, 900]);
+        assert_eq!(infos[1].adv_width_avg, 550);
+        assert_eq!(infos[1].cap_height, 700);
+    }
+
+    #[test]
+    fn test_postscript_info_without_counts() {
+        let infos = vfb(&[]).postscript_info().unwrap();
+        assert_eq!(infos[0].blue_values.len(), 14);
+        assert_eq!(infos[0].stem_snap_v.len(), 12);
+    }
+
+    #[test]
+    fn test_postscript_info_bad_count() {
+        assert!(vfb(&[(1531, 11)]).postscript_info().is_err());
+    }
+
+    #[test]
+    fn test_postscript_info_per_master() {
+        assert!(
+            vfb_with(&[(1503, vec![0x02, 0x00]), (1536, postscript_info_data())])
+                .postscript_info()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{
+    buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph::Point, glyph_record::Glyph,
+};
+use serde::Serialize;
+use std::{fs::File, io::Write};
+
+/// A freehand stroke of the sketch layer
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Stroke {
+    /// Whether the stroke is a closed path
+    pub closed: bool,
+    /// The pen width in font units
+    pub width: u32,
+    /// The points of the stroke in font units
+    pub points: Vec<Point>,
+}
+
+/// The decompiled "Glyph Sketch" entry, the freehand sketch layer of a glyph
+#[derive(Serialize, Debug)]
+pub struct Sketch {
+    pub strokes: Vec<Stroke>,
+}
+
+impl Sketch {
+    /// The bounding box of all points as minimum and maximum point, including the pen
+    /// width
+    fn bounds(&self) -> Option<(Point, Point)> {
+        let mut bounds: Option<(Point, Point)> = None;
+        for stroke in &self.strokes {
+            let w = i32::try_from(stroke.width.div_ceil(2)).unwrap_or(i32::MAX);
+            for p in &stroke.points {
+                let (min, max) = bounds.get_or_insert((*p, *p));
+                min.x = min.x.min(p.x.saturating_sub(w));
+                min.y = min.y.min(p.y.saturating_sub(w));
+                max.x = max.x.max(p.x.saturating_add(w));
+                max.y = max.y.max(p.y.saturating_add(w));
+            }
+        }
+        bounds
+    }
+
+    /// The sketch as an SVG document. The y axis is flipped, so the sketch appears the
+    /// right way up.
+    pub fn to_svg(&self) -> String {
+        let (min, max) = self.bounds().unwrap_or_default();
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n",
+            min.x,
+            -i64::from(max.y),
+            i64::from(max.x) - i64::from(min.x),
+            i64::from(max.y) - i64::from(min.y)
+        );
+        for stroke in &self.strokes {
+            let mut d: Vec<String> = vec![];
+            for (i, p) in stroke.points.iter().enumerate() {
+                let command = if i == 0 { "M" } else { "L" };
+                d.push(format!("{}{} {}", command, p.x, -i64::from(p.y)));
+            }
+            if stroke.closed {
+                d.push("Z".to_string());
+            }
+            svg.push_str(&format!(
+                "<path d=\"{}\" fill=\"none\" stroke=\"black\" stroke-width=\"{}\" \
+                stroke-linecap=\"round\" stroke-linejoin=\"round\"/>\n",
+                d.join(" "),
+                stroke.width
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// Write the sketch as an SVG document
+    pub fn write_svg<W: Write>(&self, w: &mut W) -> Result<(), VfbError> {
+        w.write_all(self.to_svg().as_bytes())
+            .map_err(VfbError::WriteError)
+    }
+
+    /// Save the sketch as an SVG file
+    pub fn save_svg(&self, path: &str) -> Result<(), VfbError> {
+        let mut file = File::create(path).map_err(VfbError::FileCreateError)?;
+        self.write_svg(&mut file)
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_glyph_sketch(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let num_strokes = self.read_count()?;
+        let mut strokes = vec![];
+        for _ in 0..num_strokes {
+            let flags = self.read_count()?;
+            let width = self.read_count()?;
+            let num_points = self.read_count()?;
+            // The points are stored relative to the previous point of the stroke
+            let mut points = vec![];
+            let mut pos = Point::default();
+            for _ in 0..num_points {
+                pos = pos.checked_add(self.read_point()?)?;
+                points.push(pos);
+            }
+            strokes.push(Stroke {
+                closed: flags & 1 != 0,
+                width,
+                points,
+            });
+        }
+        Ok(Some(VfbEntryType::Sketch(Sketch { strokes })))
+    }
+}
+
+impl Glyph {
+    /// The freehand sketch layer of the glyph
+    pub fn sketch(&self) -> Option<&Sketch> {
+        match &self.entry("Glyph Sketch")?.entry {
+            VfbEntryType::Sketch(sketch) => Some(sketch),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        buffer::VfbReader,
+        entries::VfbEntryType,
+        glyph::Point,
+        sketch::{Sketch, Stroke},
+    };
+
+    /// An open stroke with 3 points and a closed stroke with 2 points
+    pub(crate) fn sketch_data() -> Vec<u8> {
+        vec![
+            0x8d, // 2 strokes
+            0x8b, 0x95, 0x8e, 0x95, 0x95, 0x95, 0x8b, 0x8b, 0x95, // open, width 10
+            0x8c, 0x8c, 0x8d, 0x8b, 0x8b, 0x9f, 0x8b, // closed, width 1
+        ]
+    }
+
+    fn sketch() -> Sketch {
+        let bytes = sketch_data();
+        match VfbReader::new(bytes.as_slice())
+            .decompile_glyph_sketch()
+            .unwrap()
+        {
+            Some(VfbEntryType::Sketch(sketch)) => sketch,
+            _ => panic!("Not a sketch"),
+        }
+    }
+
+    #[test]
+    fn test_sketch() {
+        let sketch = sketch();
+        assert_eq!(sketch.strokes.len(), 2);
+        assert_eq!(
+            sketch.strokes[0],
+            Stroke {
+                closed: false,
+                width: 10,
+                points: vec![
+                    Point { x: 10, y: 10 },
+                    Point { x: 20, y: 10 },
+                    Point { x: 20, y: 20 }
+                ]
+            }
+        );
+        assert!(sketch.strokes[1].closed);
+        assert_eq!(sketch.strokes[1].points[1], Point { x: 20, y: 0 });
+    }
+
+    #[test]
+    fn test_sketch_overflow() {
+        // Two points with x = i32::MAX
+        let bytes = [
+            0x8c, 0x8b, 0x8c, 0x8d, 0xff, 0x7f, 0xff, 0xff, 0xff, 0x8b, 0xff, 0x7f, 0xff, 0xff,
+            0xff, 0x8b,
+        ];
+        assert!(VfbReader::new(bytes.as_slice())
+            .decompile_glyph_sketch()
+            .is_err());
+    }
+
+    #[test]
+    fn test_sketch_svg_extremes() {
+        let sketch = Sketch {
+            strokes: vec![Stroke {
+                closed: false,
+                width: u32::MAX,
+                points: vec![Point {
+                    x: i32::MIN,
+                    y: i32::MIN,
+                }],
+            }],
+        };
+        assert!(sketch.to_svg().contains("M-2147483648 2147483648"));
+    }
+
+    #[test]
+    fn test_sketch_svg() {
+        assert_eq!(
+            sketch().to_svg(),
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-1 -25 26 26\">\n\
+            <path d=\"M10 -10 L20 -10 L20 -20\" fill=\"none\" stroke=\"black\" \
+            stroke-width=\"10\" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>\n\
+            <path d=\"M0 0 L20 0 Z\" fill=\"none\" stroke=\"black\" stroke-width=\"1\" \
+            stroke-linecap=\"round\" stroke-linejoin=\"round\"/>\n\
+            </svg>\n"
+        );
+    }
+}
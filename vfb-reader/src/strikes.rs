@@ -0,0 +1,285 @@
+use crate::{
+    bitmap::Bitmap, buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph::Point,
+    glyph_record::Glyph, Vfb,
+};
+use serde::Serialize;
+use std::{fs::File, io::Write};
+
+/// A hand-edited 1-bit bitmap of a glyph for one ppem size
+#[derive(Serialize, Debug)]
+pub struct GlyphBitmap {
+    pub ppem: u32,
+    /// The advance width and height in pixels
+    pub advance: Point,
+    /// The offset of the bottom left corner of the bitmap from the glyph origin in pixels
+    pub origin: Point,
+    pub bitmap: Bitmap,
+}
+
+impl GlyphBitmap {
+    /// The offset of the top right corner of the bitmap from the glyph origin in pixels
+    fn top_right(&self) -> Result<Point, VfbError> {
+        match (
+            i32::try_from(self.bitmap.width),
+            i32::try_from(self.bitmap.height),
+        ) {
+            (Ok(x), Ok(y)) => self.origin.checked_add(Point { x, y }),
+            _ => Err(VfbError::Overflow(u32::MAX)),
+        }
+    }
+}
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    pub fn decompile_glyph_bitmaps(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let num_strikes = self.read_count()?;
+        let mut strikes = vec![];
+        for _ in 0..num_strikes {
+            let ppem = self.read_count()?;
+            let advance = self.read_point()?;
+            let origin = self.read_point()?;
+            let width = self.read_count()?;
+            let height = self.read_count()?;
+            let len = self.read_count()?;
+            let data = self.read_bytes(len.into())?;
+            strikes.push(GlyphBitmap {
+                ppem,
+                advance,
+                origin,
+                bitmap: Bitmap::new(width, height, 1, data)?,
+            });
+        }
+        Ok(Some(VfbEntryType::GlyphBitmaps(strikes)))
+    }
+}
+
+impl Glyph {
+    /// The bitmaps of the glyph, one for each ppem size
+    pub fn bitmaps(&self) -> &[GlyphBitmap] {
+        match self.entry("Glyph Bitmaps").map(|e| &e.entry) {
+            Some(VfbEntryType::GlyphBitmaps(strikes)) => strikes,
+            _ => &[],
+        }
+    }
+
+    /// The bitmap of the glyph for the given ppem size
+    pub fn bitmap(&self, ppem: u32) -> Option<&GlyphBitmap> {
+        self.bitmaps().iter().find(|b| b.ppem == ppem)
+    }
+}
+
+impl Vfb {
+    /// The ppem sizes for which glyph bitmaps are present
+    pub fn bitmap_strikes(&self) -> Vec<u32> {
+        let mut ppems: Vec<u32> = self
+            .glyphs()
+            .iter()
+            .flat_map(|g| g.bitmaps().iter().map(|b| b.ppem))
+            .collect();
+        ppems.sort();
+        ppems.dedup();
+        ppems
+    }
+
+    /// The code point of the glyph in the font's encoding, if any
+    fn encoding_code(&self, name: &str) -> Option<u16> {
+        self.entries()
+            .iter()
+            .find_map(|e| match (&e.key[..], &e.entry) {
+                ("Encoding", VfbEntryType::Encoding((code, n))) if n == name => Some(*code),
+                _ => None,
+            })
+    }
+
+    /// Write the glyph bitmaps of the given ppem size as a BDF font
+    pub fn write_bdf<W: Write>(&self, ppem: u32, w: &mut W) -> Result<(), VfbError> {
+        let chars: Vec<(&str, &GlyphBitmap)> = self
+            .glyphs()
+            .iter()
+            .filter_map(|g| Some((g.name()?, g.bitmap(ppem)?)))
+            .collect();
+
+        // The union of all bounding boxes
+        let (mut x_min, mut y_min, mut x_max, mut y_max) = (0, 0, 0, 0);
+        for (_, b) in &chars {
+            let top_right = b.top_right()?;
+            x_min = x_min.min(b.origin.x);
+            y_min = y_min.min(b.origin.y);
+            x_max = x_max.max(top_right.x);
+            y_max = y_max.max(top_right.y);
+        }
+        let (Some(width), Some(height), Some(descent)) = (
+            x_max.checked_sub(x_min),
+            y_max.checked_sub(y_min),
+            y_min.checked_neg(),
+        ) else {
+            return Err(VfbError::Overflow(u32::MAX));
+        };
+
+        let font_name = match self.entry("font_name").map(|e| &e.entry) {
+            Some(VfbEntryType::String(name)) => name.as_str(),
+            _ => "Untitled",
+        };
+
+        let mut bdf = String::new();
+        bdf.push_str("STARTFONT 2.1\n");
+        bdf.push_str(&format!("FONT {}-{}\n", font_name, ppem));
+        bdf.push_str(&format!("SIZE {} 72 72\n", ppem));
+        bdf.push_str(&format!(
+            "FONTBOUNDINGBOX {} {} {} {}\n",
+            width, height, x_min, y_min
+        ));
+        bdf.push_str("STARTPROPERTIES 2\n");
+        bdf.push_str(&format!("FONT_ASCENT {}\n", y_max));
+        bdf.push_str(&format!("FONT_DESCENT {}\n", descent));
+        bdf.push_str("ENDPROPERTIES\n");
+        bdf.push_str(&format!("CHARS {}\n", chars.len()));
+        for (name, b) in chars {
+            let encoding = self.encoding_code(name).map_or(-1, i32::from);
+            let swidth = (b.advance.x as f64 * 1000.0 / ppem as f64).round() as i32;
+            bdf.push_str(&format!("STARTCHAR {}\n", name));
+            bdf.push_str(&format!("ENCODING {}\n", encoding));
+            bdf.push_str(&format!("SWIDTH {} 0\n", swidth));
+            bdf.push_str(&format!("DWIDTH {} {}\n", b.advance.x, b.advance.y));
+            bdf.push_str(&format!(
+                "BBX {} {} {} {}\n",
+                b.bitmap.width, b.bitmap.height, b.origin.x, b.origin.y
+            ));
+            bdf.push_str("BITMAP\n");
+            for y in 0..b.bitmap.height {
+                bdf.push_str(&format!("{}\n", hex::encode_upper(b.bitmap.row(y))));
+            }
+            bdf.push_str("ENDCHAR\n");
+        }
+        bdf.push_str("ENDFONT\n");
+        w.write_all(bdf.as_bytes()).map_err(VfbError::WriteError)
+    }
+
+    /// Save the glyph bitmaps of the given ppem size as a BDF file
+    pub fn save_bdf(&self, ppem: u32, path: &str) -> Result<(), VfbError> {
+        let mut file = File::create(path).map_err(VfbError::FileCreateError)?;
+        self.write_bdf(ppem, &mut file)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        error::VfbError,
+        glyph::{tests::glyph_data, Point},
+        tests::vfb_with,
+        Vfb,
+    };
+
+    /// Bitmaps for 2 ppem sizes: 10 ppem with a 3x2 bitmap, 12 ppem with an empty one
+    pub(crate) fn glyph_bitmaps_data() -> Vec<u8> {
+        vec![
+            0x8d, // 2 strikes
+            0x95, 0x90, 0x8b, 0x8a, 0x8b, 0x8e, 0x8d, 0x8d, 0x80, 0x40, // 10 ppem
+            0x97, 0x91, 0x8b, 0x8b, 0x8b, 0x8b, 0x8b, 0x8b, // 12 ppem
+        ]
+    }
+
+    fn vfb() -> Vfb {
+        vfb_with_bitmaps(&glyph_bitmaps_data())
+    }
+
+    fn vfb_with_bitmaps(data: &[u8]) -> Vfb {
+        vfb_with(&[
+            (1500, vec![0x41, 0x00, b'A']),
+            (1026, b"MyFont-Regular".to_vec()),
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (2013, data.to_vec()),
+        ])
+    }
+
+    #[test]
+    fn test_glyph_bitmaps() {
+        let vfb = vfb();
+        let glyph = vfb.glyph("A").unwrap();
+        assert_eq!(glyph.bitmaps().len(), 2);
+        let bitmap = glyph.bitmap(10).unwrap();
+        assert_eq!(bitmap.advance, Point { x: 5, y: 0 });
+        assert_eq!(bitmap.origin, Point { x: -1, y: 0 });
+        assert_eq!(bitmap.bitmap.width, 3);
+        assert!(bitmap.bitmap.is_black(0, 0));
+        assert_eq!(vfb.bitmap_strikes(), vec![10, 12]);
+    }
+
+    /// The BITMAP section of the glyph in the BDF
+    fn bdf_bitmap(vfb: &Vfb) -> String {
+        let mut bdf = vec![];
+        vfb.write_bdf(10, &mut bdf).unwrap();
+        let bdf = String::from_utf8(bdf).unwrap();
+        let start = bdf.find("BITMAP\n").unwrap();
+        let end = bdf.find("ENDCHAR").unwrap();
+        bdf[start..end].to_string()
+    }
+
+    #[test]
+    fn test_bdf_rows() {
+        // A 3x2 bitmap with 2 bytes of padding
+        let vfb = vfb_with_bitmaps(&[
+            0x8c, 0x95, 0x90, 0x8b, 0x8a, 0x8b, 0x8e, 0x8d, 0x8f, 0x80, 0x40, 0xff, 0xff,
+        ]);
+        assert_eq!(bdf_bitmap(&vfb), "BITMAP\n80\n40\n");
+        // An empty bitmap with padding
+        let vfb = vfb_with_bitmaps(&[
+            0x8c, 0x95, 0x90, 0x8b, 0x8b, 0x8b, 0x8b, 0x8b, 0x8d, 0xff, 0xff,
+        ]);
+        assert_eq!(bdf_bitmap(&vfb), "BITMAP\n");
+    }
+
+    #[test]
+    fn test_bdf_overflow() {
+        // A 3x2 bitmap with its origin at x = i32::MAX, so its right edge is out of range
+        let vfb = vfb_with_bitmaps(&[
+            0x8c, 0x95, 0x90, 0x8b, 0xff, 0x7f, 0xff, 0xff, 0xff, 0x8b, 0x8e, 0x8d, 0x8d, 0x80,
+            0x40,
+        ]);
+        assert!(matches!(
+            vfb.write_bdf(10, &mut vec![]),
+            Err(VfbError::Overflow(_))
+        ));
+        // A bitmap with its origin at y = i32::MIN, so the font height is out of range
+        let vfb = vfb_with_bitmaps(&[
+            0x8c, 0x95, 0x90, 0x8b, 0x8a, 0xff, 0x80, 0x00, 0x00, 0x00, 0x8e, 0x8d, 0x8d, 0x80,
+            0x40,
+        ]);
+        assert!(matches!(
+            vfb.write_bdf(10, &mut vec![]),
+            Err(VfbError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_bdf() {
+        let mut bdf = vec![];
+        vfb().write_bdf(10, &mut bdf).unwrap();
+        assert_eq!(
+            String::from_utf8(bdf).unwrap(),
+            "STARTFONT 2.1\n\
+            FONT MyFont-Regular-10\n\
+            SIZE 10 72 72\n\
+            FONTBOUNDINGBOX 3 2 -1 0\n\
+            STARTPROPERTIES 2\n\
+            FONT_ASCENT 2\n\
+            FONT_DESCENT 0\n\
+            ENDPROPERTIES\n\
+            CHARS 1\n\
+            STARTCHAR A\n\
+            ENCODING 65\n\
+            SWIDTH 500 0\n\
+            DWIDTH 5 0\n\
+            BBX 3 2 -1 0\n\
+            BITMAP\n\
+            80\n\
+            40\n\
+            ENDCHAR\n\
+            ENDFONT\n"
+        );
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{entry::VfbEntry, error::VfbError, vfb_constants::GLYPH_KEYS, Vfb};
+use serde::Serialize;
+
+/// The type of a block of entries
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlockType {
+    /// The top level of the tree, which is not delimited by markers
+    Root,
+    File,
+    Font,
+    Names,
+    FontInfo,
+    MMFontInfo,
+    MMKerning,
+    /// A "Glyph" entry and the entries that belong to it
+    Glyph,
+}
+
+impl BlockType {
+    /// The block type started by the entry with the given key
+    fn from_start_key(key: u16) -> Option<Self> {
+        match key {
+            1 => Some(BlockType::File),
+            4 => Some(BlockType::Font),
+            262 => Some(BlockType::Names),
+            257 => Some(BlockType::FontInfo),
+            271 => Some(BlockType::MMFontInfo),
+            272 => Some(BlockType::MMKerning),
+            _ => None,
+        }
+    }
+
+    /// The block type ended by the entry with the given key
+    fn from_end_key(key: u16) -> Option<Self> {
+        match key {
+            2 => Some(BlockType::File),
+            5 => Some(BlockType::Font),
+            _ => key.checked_sub(256).and_then(Self::from_start_key),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub enum TreeNode<'a> {
+    Entry(&'a VfbEntry),
+    Block(Block<'a>),
+}
+
+/// A block of entries. For blocks delimited by markers, the markers are not included in
+/// the children.
+#[derive(Serialize)]
+pub struct Block<'a> {
+    pub block_type: BlockType,
+    /// The index of the entry that starts the block
+    pub start: usize,
+    /// The index of the entry that ends the block, if it is present in the entries
+    pub end: Option<usize>,
+    pub children: Vec<TreeNode<'a>>,
+}
+
+impl<'a> Block<'a> {
+    fn new(block_type: BlockType, start: usize) -> Self {
+        Self {
+            block_type,
+            start,
+            end: None,
+            children: vec![],
+        }
+    }
+
+    /// The child blocks of this block
+    pub fn blocks(&self) -> impl Iterator<Item = &Block<'a>> {
+        self.children.iter().filter_map(|c| match c {
+            TreeNode::Block(block) => Some(block),
+            TreeNode::Entry(_) => None,
+        })
+    }
+
+    /// The entries that are direct children of this block
+    pub fn entries(&self) -> impl Iterator<Item = &'a VfbEntry> + '_ {
+        self.children.iter().filter_map(|c| match c {
+            TreeNode::Entry(entry) => Some(*entry),
+            TreeNode::Block(_) => None,
+        })
+    }
+
+    /// Find the first block of the given type in this block or its descendants
+    pub fn find(&self, block_type: BlockType) -> Option<&Block<'a>> {
+        if self.block_type == block_type {
+            return Some(self);
+        }
+        self.blocks().find_map(|b| b.find(block_type))
+    }
+
+    /// Find all blocks of the given type in this block or its descendants
+    pub fn find_all(&self, block_type: BlockType) -> Vec<&Block<'a>> {
+        let mut blocks = vec![];
+        if self.block_type == block_type {
+            blocks.push(self);
+        }
+        for block in self.blocks() {
+            blocks.extend(block.find_all(block_type));
+        }
+        blocks
+    }
+
+    /// Find the block that starts at the entry with the given index
+    pub fn block_at(&self, index: usize) -> Option<&Block<'a>> {
+        if self.block_type != BlockType::Root && self.start == index {
+            return Some(self);
+        }
+        self.blocks().find_map(|b| b.block_at(index))
+    }
+}
+
+impl Vfb {
+    /// Build a tree view of the entries, grouped into blocks by their start and end
+    /// markers. The entries that belong to a glyph are grouped into a glyph block.
+    ///
+    /// The end markers of the font and file blocks are not part of the entries, so these
+    /// blocks may remain open at the end. Any other open block is an error, as are end
+    /// markers that don't match the open block.
+    pub fn tree(&self) -> Result<Block<'_>, VfbError> {
+        let mut stack = vec![Block::new(BlockType::Root, 0)];
+        for (i, entry) in self.all_entries().into_iter().enumerate() {
+            let key = entry.numeric_key()?;
+
+            // A glyph block ends at any entry that doesn't belong to the glyph
+            if stack.last().map(|b| b.block_type) == Some(BlockType::Glyph)
+                && !GLYPH_KEYS.contains(&entry.key.as_str())
+            {
+                close_block(&mut stack);
+            }
+
+            if let Some(block_type) = BlockType::from_start_key(key) {
+                stack.push(Block::new(block_type, i));
+            } else if let Some(block_type) = BlockType::from_end_key(key) {
+                let open = stack.last().map(|b| b.block_type);
+                if open != Some(block_type) {
+                    return Err(VfbError::MismatchedMarker(format!(
+                        "End of {:?} at entry {}, but the open block is {:?}",
+                        block_type, i, open
+                    )));
+                }
+                if let Some(block) = stack.last_mut() {
+                    block.end = Some(i);
+                }
+                close_block(&mut stack);
+            } else if entry.key == "Glyph" {
+                stack.push(Block::new(BlockType::Glyph, i));
+            } else if let Some(block) = stack.last_mut() {
+                block.children.push(TreeNode::Entry(entry));
+            }
+        }
+
+        while stack.len() > 1 {
+            let open = stack.last().map(|b| b.block_type);
+            match open {
+                Some(BlockType::Glyph) | Some(BlockType::Font) | Some(BlockType::File) => {
+                    close_block(&mut stack)
+                }
+                _ => {
+                    return Err(VfbError::MismatchedMarker(format!(
+                        "Block {:?} is not closed",
+                        open
+                    )))
+                }
+            }
+        }
+        stack
+            .pop()
+            .ok_or_else(|| VfbError::MismatchedMarker("No root block".to_string()))
+    }
+}
+
+/// Move the innermost open block to the children of its parent
+fn close_block(stack: &mut Vec<Block<'_>>) {
+    if let Some(block) = stack.pop() {
+        if let Some(parent) = stack.last_mut() {
+            parent.children.push(TreeNode::Block(block));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        glyph::tests::{base_glyph_data, glyph_data},
+        tests::vfb_with,
+        tree::BlockType,
+    };
+
+    #[test]
+    fn test_tree() {
+        let vfb = vfb_with(&[
+            (1, vec![]),
+            (4, vec![]),
+            (262, vec![]),
+            (1500, vec![0x00, 0x00, 0x41]),
+            (518, vec![]),
+            (257, vec![]),
+            (1503, vec![0x02, 0x00]),
+            (513, vec![]),
+            (2001, glyph_data()),
+            (2012, vec![0x01, 0x00]),
+            (2001, base_glyph_data()),
+            (272, vec![]),
+            (528, vec![]),
+        ]);
+        let tree = vfb.tree().unwrap();
+        let font = tree.find(BlockType::Font).unwrap();
+        assert_eq!(font.start, 1);
+        assert_eq!(font.end, None);
+        let font_info = font.find(BlockType::FontInfo).unwrap();
+        assert_eq!(font_info.start, 5);
+        assert_eq!(font_info.end, Some(7));
+        assert_eq!(font_info.entries().next().unwrap().key, "Master Count");
+        let glyphs = tree.find_all(BlockType::Glyph);
+        assert_eq!(glyphs.len(), 2);
+        assert_eq!(glyphs[0].children.len(), 1);
+        assert_eq!(glyphs[0].entries().next().unwrap().key, "mark");
+        assert_eq!(tree.block_at(10).unwrap().block_type, BlockType::Glyph);
+        assert_eq!(tree.block_at(11).unwrap().block_type, BlockType::MMKerning);
+    }
+
+    #[test]
+    fn test_tree_without_markers() {
+        let vfb = vfb_with(&[(1503, vec![0x02, 0x00]), (2001, glyph_data())]);
+        let tree = vfb.tree().unwrap();
+        assert_eq!(tree.block_type, BlockType::Root);
+        assert_eq!(tree.children.len(), 2);
+    }
+
+    #[test]
+    fn test_tree_mismatched_marker() {
+        let vfb = vfb_with(&[(262, vec![]), (513, vec![])]);
+        assert!(vfb.tree().is_err());
+    }
+
+    #[test]
+    fn test_tree_unclosed_block() {
+        let vfb = vfb_with(&[(4, vec![]), (257, vec![])]);
+        assert!(vfb.tree().is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{buffer::VfbReader, entries::VfbEntryType, error::VfbError, glyph_record::Glyph, Vfb};
+use std::collections::BTreeMap;
+
+impl<R> VfbReader<R>
+where
+    R: std::io::Read,
+{
+    /// Decompile the "unicodes" entry, a list of u16 code points in the BMP. The code
+    /// points are kept as stored, legacy sources may contain surrogates.
+    pub fn decompile_unicodes(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let bytes = self.read_bytes_remainder()?;
+        if bytes.len() % 2 != 0 {
+            return Err(VfbError::BadValue(
+                format!("Unicodes of {} bytes", bytes.len()),
+                "a multiple of 2 bytes".to_string(),
+            ));
+        }
+        let unicodes = bytes
+            .chunks(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]).into())
+            .collect();
+        Ok(Some(VfbEntryType::Unicodes(unicodes)))
+    }
+
+    /// Decompile the "Glyph Unicode Non-BMP" entry, a list of u32 code points
+    pub fn decompile_unicodes_non_bmp(&mut self) -> Result<Option<VfbEntryType>, VfbError> {
+        let bytes = self.read_bytes_remainder()?;
+        if bytes.len() % 4 != 0 {
+            return Err(VfbError::BadValue(
+                format!("Non-BMP unicodes of {} bytes", bytes.len()),
+                "a multiple of 4 bytes".to_string(),
+            ));
+        }
+        let unicodes = bytes
+            .chunks(4)
+            .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        Ok(Some(VfbEntryType::Unicodes(unicodes)))
+    }
+}
+
+impl Glyph {
+    /// The code points of the glyph, the BMP code points from "unicodes" followed by
+    /// those from "Glyph Unicode Non-BMP". Values that are not valid chars are skipped,
+    /// see `invalid_unicodes`.
+    pub fn unicodes(&self) -> Vec<char> {
+        self.raw_unicodes()
+            .into_iter()
+            .filter_map(char::from_u32)
+            .collect()
+    }
+
+    /// The code points of the glyph that are not valid chars, e.g. surrogates
+    pub fn invalid_unicodes(&self) -> Vec<u32> {
+        self.raw_unicodes()
+            .into_iter()
+            .filter(|u| char::from_u32(*u).is_none())
+            .collect()
+    }
+
+    /// The code points of the glyph as stored
+    pub fn raw_unicodes(&self) -> Vec<u32> {
+        ["unicodes", "Glyph Unicode Non-BMP"]
+            .iter()
+            .filter_map(|key| match self.entry(key).map(|e| &e.entry) {
+                Some(VfbEntryType::Unicodes(unicodes)) => Some(unicodes),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+}
+
+impl Vfb {
+    /// A mapping from code points to glyphs. If several glyphs have the same code
+    /// point, the first one is used.
+    pub fn cmap(&self) -> BTreeMap<char, &Glyph> {
+        let mut cmap = BTreeMap::new();
+        for glyph in self.glyphs() {
+            for c in glyph.unicodes() {
+                cmap.entry(c).or_insert(glyph);
+            }
+        }
+        cmap
+    }
+
+    /// The glyph for the given code point
+    pub fn glyph_for_char(&self, c: char) -> Option<&Glyph> {
+        self.glyphs().iter().find(|g| g.unicodes().contains(&c))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use crate::{
+        buffer::VfbReader,
+        glyph::tests::{base_glyph_data, glyph_data},
+        tests::vfb_with,
+    };
+
+    /// U+0041 and U+0391
+    pub(crate) fn unicodes_data() -> Vec<u8> {
+        vec![0x41, 0x00, 0x91, 0x03]
+    }
+
+    /// U+1D400
+    pub(crate) fn unicodes_non_bmp_data() -> Vec<u8> {
+        vec![0x00, 0xd4, 0x01, 0x00]
+    }
+
+    #[test]
+    fn test_unicodes() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (1250, unicodes_data()),
+            (1253, unicodes_non_bmp_data()),
+            (2001, base_glyph_data()),
+            (1250, vec![0x42, 0x00, 0x41, 0x00]),
+        ]);
+
+        assert_eq!(
+            vfb.glyph("A").unwrap().unicodes(),
+            vec!['A', '\u{391}', '\u{1d400}']
+        );
+        let cmap = vfb.cmap();
+        assert_eq!(cmap.len(), 4);
+        assert_eq!(cmap[&'A'].name(), Some("A"));
+        assert_eq!(cmap[&'B'].name(), Some("B"));
+        assert_eq!(vfb.glyph_for_char('\u{1d400}').unwrap().name(), Some("A"));
+        assert!(vfb.glyph_for_char('C').is_none());
+    }
+
+    #[test]
+    fn test_unicodes_invalid() {
+        let bytes = [0x41, 0x00, 0x42];
+        assert!(VfbReader::new(bytes.as_slice())
+            .decompile_unicodes()
+            .is_err());
+        let bytes = [0x00, 0x00, 0x11];
+        assert!(VfbReader::new(bytes.as_slice())
+            .decompile_unicodes_non_bmp()
+            .is_err());
+    }
+
+    #[test]
+    fn test_unicodes_surrogate() {
+        let vfb = vfb_with(&[
+            (1503, vec![0x02, 0x00]),
+            (2001, glyph_data()),
+            (1250, vec![0x41, 0x00, 0x00, 0xd8]),
+            (1253, vec![0x00, 0x00, 0x11, 0x00]),
+        ]);
+
+        let glyph = vfb.glyph("A").unwrap();
+        assert_eq!(glyph.unicodes(), vec!['A']);
+        assert_eq!(glyph.invalid_unicodes(), vec![0xd800, 0x110000]);
+        assert_eq!(glyph.raw_unicodes(), vec![0x41, 0xd800, 0x110000]);
+        assert_eq!(vfb.cmap().len(), 1);
+    }
+}
@@ -2,11 +2,14 @@ use phf::phf_map;
 
 pub static VFB_KEYS: phf::Map<&'static str, &'static str> = phf_map! {
     // Sorted by appearance in the VFB
+    "1" => "1",  // Start of block: File
+    "4" => "4",  // Start of block: Font
+    "262" => "262",  // Start of block: Names
     "1501" => "Encoding Default",
     "1500" => "Encoding",
     "1502" => "1502",
-    "518" => "518",  // maybe marker? has 0 bytes data
-    "257" => "257",
+    "518" => "518",  // End of block: Names
+    "257" => "257",  // Start of block: Font Info
     "1026" => "font_name",  // psn
     "1503" => "Master Count",
     "1517" => "weight_vector",  // Default Weight Vector, one value per master
@@ -116,8 +119,8 @@ pub static VFB_KEYS: phf::Map<&'static str, &'static str> = phf_map! {
     // font.classes
     "1277" => "OpenType Class",  // OpenType Class
 
-    "513" => "513",
-    "271" => "271",
+    "513" => "513",  // End of block: Font Info
+    "271" => "271",  // Start of block: MM Font Info
     "1513" => "Axis Count",
     "1514" => "Axis Name",
     "1523" => "Anisotropic Interpolation Mappings",
@@ -134,7 +137,7 @@ pub static VFB_KEYS: phf::Map<&'static str, &'static str> = phf_map! {
     // Repeat PostScript Info for each master:
     "1536" => "PostScript Info",
 
-    "527" => "527",
+    "527" => "527",  // End of block: MM Font Info
     "1294" => "Global Guides",
     "1296" => "Global Guide Properties",
     "1295" => "Global Mask",
@@ -146,7 +149,7 @@ pub static VFB_KEYS: phf::Map<&'static str, &'static str> = phf_map! {
     "2008" => "Links",
     "2007" => "image",  // Background Bitmap
     "2013" => "Glyph Bitmaps",
-    "2023" => "2023",  // 1 encoded value per master
+    "2023" => "2023",  // 1 encoded value per master, VSB in FILEFORMAT.md
     "2019" => "Glyph Sketch",
     "2010" => "Glyph Hinting Options",
     "2009" => "mask",  // Mask
@@ -169,9 +172,36 @@ pub static VFB_KEYS: phf::Map<&'static str, &'static str> = phf_map! {
     "1744" => "Export Options",
     "1742" => "Mapping Mode",
 
-    // Not seen in FontNames.vfb:
-    "1410" => "1410",
+    // Repeat for each MM kerning pair:
+    "272" => "272",  // Start of block: MM Kerning
+    "1410" => "MM Kern Pair",
+    "528" => "528",  // End of block: MM Kerning
 
-    // File end marker:
+    // End of block: Font, the entries that follow are not read:
     "5" => "EOF",
+    "2" => "2",  // End of block: File
 };
+
+/// The keys of the entries that belong to the preceding "Glyph" entry
+pub static GLYPH_KEYS: [&str; 20] = [
+    "Links",
+    "image",
+    "Glyph Bitmaps",
+    "2023",
+    "Glyph Sketch",
+    "Glyph Hinting Options",
+    "mask",
+    "mask.metrics",
+    "mask.metrics_mm",
+    "Glyph Origin",
+    "unicodes",
+    "2034",
+    "Glyph Unicode Non-BMP",
+    "mark",
+    "glyph.customdata",
+    "glyph.note",
+    "Glyph GDEF Data",
+    "Glyph Anchors Supplemental",
+    "Glyph Anchors MM",
+    "Glyph Guide Properties",
+];
@@ -0,0 +1,278 @@
+use encoding_rs::WINDOWS_1252;
+use std::{
+    collections::HashMap,
+    io::{prelude::*, BufWriter},
+};
+
+use crate::error::VfbError;
+
+const VFB_UNICODE_STRINGS: bool = false;
+
+pub struct VfbWriter<W: std::io::Write> {
+    writer: BufWriter<W>,
+    roundtrip: bool,
+}
+impl<W> VfbWriter<W>
+where
+    W: std::io::Write,
+{
+    pub fn new(writer: W) -> Self {
+        VfbWriter {
+            writer: BufWriter::new(writer),
+            roundtrip: false,
+        }
+    }
+
+    pub(crate) fn writer(&mut self) -> &mut BufWriter<W> {
+        &mut self.writer
+    }
+
+    /// In round-trip mode, entries are written using their original binary data if
+    /// they have any, so an unmodified VFB is written back byte-for-byte.
+    pub fn set_roundtrip(&mut self, roundtrip: bool) {
+        self.roundtrip = roundtrip;
+    }
+
+    pub fn roundtrip(&self) -> bool {
+        self.roundtrip
+    }
+
+    /// Flush the buffer and return the underlying writer
+    pub fn into_inner(self) -> Result<W, VfbError> {
+        self.writer
+            .into_inner()
+            .map_err(|e| VfbError::WriteError(e.into_error()))
+    }
+
+    /// Flush the buffer to the underlying writer
+    pub fn flush(&mut self) -> Result<(), VfbError> {
+        self.writer().flush().map_err(VfbError::WriteError)
+    }
+
+    /// Write the given bytes to the buffer
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), VfbError> {
+        self.writer().write_all(bytes).map_err(VfbError::WriteError)
+    }
+
+    /// Write a string to the buffer, without any length information
+    pub fn write_str(&mut self, s: &str) -> Result<(), VfbError> {
+        if VFB_UNICODE_STRINGS {
+            self.write_bytes(s.as_bytes())
+        } else {
+            let (buf, _, _) = WINDOWS_1252.encode(s);
+            self.write_bytes(&buf)
+        }
+    }
+
+    /// Write a u8 value to the buffer
+    pub fn write_u8(&mut self, value: u8) -> Result<(), VfbError> {
+        self.write_bytes(&[value])
+    }
+
+    /// Write a u16 value to the buffer
+    pub fn write_u16(&mut self, value: u16) -> Result<(), VfbError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    /// Write a u32 value to the buffer
+    pub fn write_u32(&mut self, value: u32) -> Result<(), VfbError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    /// Write an "encoded value" to the buffer, using the shortest possible form
+    pub fn write_value(&mut self, value: i32) -> Result<(), VfbError> {
+        self.write_bytes(&encode_value(value))
+    }
+
+    /// Write a key-value map to the buffer. The keys are u8, the values are written as
+    /// "encoded values". The keys are written in ascending order, followed by a 0 key
+    /// that terminates the map.
+    ///
+    /// This is the inverse of `VfbReader::read_key_value_map`.
+    pub fn write_key_value_map(&mut self, map: &HashMap<u8, i32>) -> Result<(), VfbError> {
+        self.write_bytes(&encode_key_value_map(map)?)
+    }
+}
+
+/// Encode a value as an "encoded value", picking the shortest of the 1-, 2- and 5-byte
+/// forms like FontLab does.
+///
+/// This is the inverse of `VfbReader::read_value`, see there for the format.
+pub fn encode_value(value: i32) -> Vec<u8> {
+    match value {
+        // The integer values from −107 through 107 inclusive are encoded in a single
+        // byte as v + 139.
+        -107..=107 => vec![(value + 139) as u8],
+        // The integer values between 108 and 1131 inclusive are encoded in 2 bytes. The
+        // first byte is between 247 and 250 inclusive.
+        108..=1131 => {
+            let v = value - 108;
+            vec![(v / 256 + 247) as u8, (v % 256) as u8]
+        }
+        // The integer values between −1131 and −108 inclusive are encoded in 2 bytes. The
+        // first byte is between 251 and 254 inclusive.
+        -1131..=-108 => {
+            let v = -value - 108;
+            vec![(v / 256 + 251) as u8, (v % 256) as u8]
+        }
+        // Any other value is encoded as 255 followed by the big-endian 32-bit integer.
+        _ => {
+            let mut bytes = vec![255];
+            bytes.extend(value.to_be_bytes());
+            bytes
+        }
+    }
+}
+
+/// Encode a key-value map, see `VfbWriter::write_key_value_map`
+pub fn encode_key_value_map(map: &HashMap<u8, i32>) -> Result<Vec<u8>, VfbError> {
+    let mut keys: Vec<&u8> = map.keys().collect();
+    keys.sort();
+    let mut bytes = vec![];
+    for k in keys {
+        if *k == 0 {
+            return Err(VfbError::BadValue(
+                "Key 0 in key-value map".to_string(),
+                "Keys between 1 and 255, 0 is the end marker".to_string(),
+            ));
+        }
+        bytes.push(*k);
+        bytes.extend(encode_value(map[k]));
+    }
+    bytes.push(0);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        buffer::VfbReader,
+        entries::VfbEntryType,
+        entry::VfbEntry,
+        writer::{encode_key_value_map, encode_value, VfbWriter},
+    };
+
+    fn get_writer() -> VfbWriter<Vec<u8>> {
+        VfbWriter::new(Vec::new())
+    }
+
+    fn write_value(value: i32) -> Vec<u8> {
+        let mut w = get_writer();
+        w.write_value(value).unwrap();
+        w.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_write_value_1b() {
+        assert_eq!(write_value(-107), vec![0x20]);
+        assert_eq!(write_value(0), vec![0x8b]);
+        assert_eq!(write_value(107), vec![0xf6]);
+    }
+
+    #[test]
+    fn test_write_value_2b() {
+        assert_eq!(write_value(108), vec![0xf7, 0x00]);
+        assert_eq!(write_value(1131), vec![0xfa, 0xff]);
+        assert_eq!(write_value(-108), vec![0xfb, 0x00]);
+        assert_eq!(write_value(-1131), vec![0xfe, 0xff]);
+    }
+
+    #[test]
+    fn test_write_value_5b() {
+        assert_eq!(write_value(4096), vec![0xff, 0x00, 0x00, 0x10, 0x00]);
+        assert_eq!(write_value(-4097), vec![0xff, 0xff, 0xff, 0xef, 0xff]);
+    }
+
+    #[test]
+    fn test_encode_value_boundaries() {
+        assert_eq!(encode_value(-108).len(), 2);
+        assert_eq!(encode_value(-107).len(), 1);
+        assert_eq!(encode_value(107).len(), 1);
+        assert_eq!(encode_value(108).len(), 2);
+        assert_eq!(encode_value(1131).len(), 2);
+        assert_eq!(encode_value(1132), vec![0xff, 0x00, 0x00, 0x04, 0x6c]);
+        assert_eq!(encode_value(-1131).len(), 2);
+        assert_eq!(encode_value(-1132), vec![0xff, 0xff, 0xff, 0xfb, 0x94]);
+        assert_eq!(encode_value(i32::MAX), vec![0xff, 0x7f, 0xff, 0xff, 0xff]);
+        assert_eq!(encode_value(i32::MIN), vec![0xff, 0x80, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_encode_value_roundtrip() {
+        for value in (-5000..5000).chain([i32::MIN, i32::MAX]) {
+            let bytes = encode_value(value);
+            assert_eq!(
+                VfbReader::new(bytes.as_slice()).read_value().unwrap(),
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_key_value_map() {
+        assert_eq!(
+            encode_key_value_map(&HashMap::from([(3, 4616), (1, 1), (2, 0x05000480)])).unwrap(),
+            vec![
+                0x01, 0x8c, 0x02, 0xff, 0x05, 0x00, 0x04, 0x80, 0x03, 0xff, 0x00, 0x00, 0x12, 0x08,
+                0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_value_map_key_0() {
+        assert!(encode_key_value_map(&HashMap::from([(0, 1)])).is_err());
+    }
+
+    #[test]
+    fn test_write_entry() {
+        let entry =
+            VfbEntry::new_from_data("Encoding".to_string(), vec![0x34, 0x00, 0x66], Some(1))
+                .unwrap();
+        let mut w = get_writer();
+        w.write_entry(&entry).unwrap();
+        assert_eq!(
+            w.into_inner().unwrap(),
+            vec![0xdc, 0x05, 0x03, 0x00, 0x34, 0x00, 0x66]
+        );
+    }
+
+    #[test]
+    fn test_write_entry_u32_size() {
+        let entry =
+            VfbEntry::new_from_data("features".to_string(), vec![0x20; 0x10000], Some(1)).unwrap();
+        let mut w = get_writer();
+        w.write_entry(&entry).unwrap();
+        let bytes = w.into_inner().unwrap();
+        assert_eq!(bytes[..6], [0xfc, 0x84, 0x00, 0x00, 0x01, 0x00]);
+        assert_eq!(bytes.len(), 6 + 0x10000);
+    }
+
+    #[test]
+    fn test_write_decompiled_entry_u32_size() {
+        let data = vec![0x20; 70000];
+        let entry = VfbEntry::new_from_data("note".to_string(), data.clone(), Some(1)).unwrap();
+        assert!(matches!(entry.entry(), VfbEntryType::String(s) if s.len() == 70000));
+        let mut w = get_writer();
+        w.write_entry(&entry).unwrap();
+        let bytes = w.into_inner().unwrap();
+        assert_eq!(bytes[..6], [0xe9, 0x87, 0x70, 0x11, 0x01, 0x00]);
+        assert_eq!(bytes[6..], data);
+    }
+
+    #[test]
+    fn test_write_entry_trailing_bytes() {
+        // A uint16 entry with an unexpected third byte stays raw and is written unchanged
+        let entry =
+            VfbEntry::new_from_data("upm".to_string(), vec![0xe8, 0x03, 0x01], Some(1)).unwrap();
+        assert!(matches!(entry.entry(), VfbEntryType::Raw(_)));
+        let mut w = get_writer();
+        w.write_entry(&entry).unwrap();
+        assert_eq!(
+            w.into_inner().unwrap(),
+            vec![0x6f, 0x04, 0x03, 0x00, 0xe8, 0x03, 0x01]
+        );
+    }
+}